@@ -0,0 +1,35 @@
+//! Compares visiting each member of a large class with `entity_mut_class`,
+//! which copies the class, and `for_each_in_class`, which does not.
+
+mod util;
+
+use bevy_ecs::prelude::*;
+use bevy_mod_props::prelude::*;
+use util::bench;
+
+const ENTITIES: usize = 10_000;
+
+fn main() {
+    let mut world = World::new();
+    world.init_resource::<Registry>();
+    for index in 0..ENTITIES {
+        world.spawn((Class::new("orc"), Props::new().with("health", index as f32)));
+    }
+
+    bench("visit 10k-entity class (entity_mut_class)", || {
+        let mut orcs = world.entity_mut_class("orc");
+        while let Some(mut orc) = orcs.next() {
+            let mut props = orc.get_mut::<Props>().unwrap();
+            let health = props.get::<f32>("health");
+            props.set("health", health + 1.0);
+        }
+    });
+
+    bench("visit 10k-entity class (for_each_in_class)", || {
+        world.for_each_in_class("orc", |mut orc| {
+            let mut props = orc.get_mut::<Props>().unwrap();
+            let health = props.get::<f32>("health");
+            props.set("health", health + 1.0);
+        });
+    });
+}
@@ -0,0 +1,38 @@
+//! Compares scanning a property in `Props` components with scanning a
+//! `PropColumn`.
+
+mod util;
+
+use bevy_ecs::prelude::*;
+use bevy_mod_props::prelude::*;
+use util::bench;
+
+const ENTITIES: usize = 100_000;
+
+fn main() {
+    let mut world = World::new();
+    for index in 0..ENTITIES {
+        world
+            .spawn_empty()
+            .set_prop("health", index as f32)
+            .set_prop("mana", 10.0)
+            .set_prop("name", "orc");
+    }
+    let mut query = world.query::<&Props>();
+    bench("sum 100k props (Props components)", || {
+        query
+            .iter(&world)
+            .map(|props| props.get::<f32>("health"))
+            .sum::<f32>()
+    });
+
+    let column = PropColumn::<f32>::promote(&mut world, "health");
+    bench("sum 100k props (PropColumn)", || {
+        column.values().sum::<f32>()
+    });
+
+    let mut column = column;
+    bench("prune 100k-entity PropColumn", || {
+        column.prune(world.entities())
+    });
+}
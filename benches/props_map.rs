@@ -0,0 +1,31 @@
+//! Compares getting and setting properties in `Props`, which is sorted, and
+//! `HashedProps`, which is hashed.
+
+mod util;
+
+use bevy_mod_props::prelude::*;
+use util::bench;
+
+fn main() {
+    for count in [8, 128, 1024] {
+        let names: Vec<Estr> = (0..count)
+            .map(|index| Estr::from(format!("prop_{index}").as_str()))
+            .collect();
+
+        let mut props = Props::new();
+        bench(&format!("get/set {count} props (Props)"), || {
+            for &name in &names {
+                let value = props.get::<f32>(name);
+                props.set(name, value + 1.0);
+            }
+        });
+
+        let mut props = HashedProps::new();
+        bench(&format!("get/set {count} props (HashedProps)"), || {
+            for &name in &names {
+                let value = props.get::<f32>(name);
+                props.set(name, value + 1.0);
+            }
+        });
+    }
+}
@@ -0,0 +1,25 @@
+//! A minimal timing harness shared by the benchmarks.
+//!
+//! Run the benchmarks with `cargo bench`. Each one is run a few times to warm
+//! up, then timed over a number of iterations, and the mean time per
+//! iteration is printed.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WARMUP: u32 = 3;
+const ITERATIONS: u32 = 20;
+
+/// Times a function, printing and returning the mean time per iteration.
+pub fn bench<R>(name: &str, mut f: impl FnMut() -> R) -> Duration {
+    for _ in 0..WARMUP {
+        black_box(f());
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let mean = start.elapsed() / ITERATIONS;
+    println!("{name:<48} {mean:>12.2?}");
+    mean
+}
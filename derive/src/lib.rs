@@ -0,0 +1,69 @@
+//! Provides derive macros for `bevy_mod_props`. These are re-exported by the
+//! main crate when the `derive` feature is enabled.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields};
+
+/// Implements `IntoProps` for a struct with named fields, mapping each field
+/// to a property with the same name.
+#[proc_macro_derive(IntoProps)]
+pub fn derive_into_props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match into_props(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn into_props(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "`IntoProps` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "`IntoProps` can only be derived for structs with named fields",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    // Spans are taken from the field types, so that fields which can't be
+    // converted to and from values report errors at the field
+    let mut sets = Vec::new();
+    let mut gets = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let key = ident.to_string();
+        let ty = &field.ty;
+        sets.push(quote_spanned! {ty.span()=>
+            props.set(#key, ::bevy_mod_props::props::Value::from(
+                ::core::clone::Clone::clone(&self.#ident)
+            ));
+        });
+        gets.push(quote_spanned! {ty.span()=>
+            #ident: props.get::<#ty>(#key),
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_mod_props::props::IntoProps for #name #type_generics #where_clause {
+            fn to_props(&self) -> ::bevy_mod_props::props::Props {
+                let mut props = ::bevy_mod_props::props::Props::new();
+                #(#sets)*
+                props
+            }
+
+            fn from_props(props: &::bevy_mod_props::props::Props) -> Self {
+                #name {
+                    #(#gets)*
+                }
+            }
+        }
+    })
+}
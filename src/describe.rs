@@ -0,0 +1,128 @@
+//! Gathers everything the crate knows about an entity into one snapshot, for
+//! debugging and bug reports.
+
+use std::fmt;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+use estr::Estr;
+
+use crate::links::LinksWorldExt;
+use crate::props::Props;
+use crate::registry::{Class, Classes, Identity};
+
+/// A snapshot of an entity's name, classes, props, and links. Created with
+/// [`DescribeExt::describe`].
+///
+/// The [`Display`][fmt::Display] impl prints the snapshot over several lines,
+/// with props and links sorted by name.
+#[derive(Clone, Debug)]
+pub struct EntityDescription {
+    pub entity: Entity,
+    pub name: Option<Estr>,
+    pub class: Option<Estr>,
+    /// Additional classes from the entity's [`Classes`], sorted.
+    pub classes: Vec<Estr>,
+    pub props: Props,
+    /// Links by name, sorted by name and then by target.
+    pub links: Vec<(Estr, Vec<Entity>)>,
+}
+
+impl fmt::Display for EntityDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.entity)?;
+        if let Some(name) = self.name {
+            write!(f, " \"{name}\"")?;
+        }
+        if let Some(class) = self.class {
+            write!(f, " ({class})")?;
+        }
+        writeln!(f)?;
+        if !self.classes.is_empty() {
+            let classes: Vec<&str> = self.classes.iter().map(Estr::as_str).collect();
+            writeln!(f, "  classes: {}", classes.join(", "))?;
+        }
+        if self.props.iter().next().is_some() {
+            writeln!(f, "  props:")?;
+            for (name, value) in self.props.iter() {
+                writeln!(f, "    {name}: {value}")?;
+            }
+        }
+        if !self.links.is_empty() {
+            writeln!(f, "  links:")?;
+            for (name, targets) in &self.links {
+                let targets: Vec<String> = targets.iter().map(Entity::to_string).collect();
+                writeln!(f, "    {name}: {}", targets.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adds [`describe`][DescribeExt::describe] to [`World`].
+pub trait DescribeExt {
+    /// Returns a snapshot of everything known about an entity. An entity which
+    /// does not exist is described as having no name, classes, props, or
+    /// links.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let gandalf = world.spawn_named_class("gandalf", "wizard").id();
+    /// let bilbo = world
+    ///     .spawn_named_class("bilbo", "hobbit")
+    ///     .add_class("burglar")
+    ///     .set_prop("has_ring", true)
+    ///     .set_prop("health", 100.0)
+    ///     .set_link("talking_to", gandalf)
+    ///     .id();
+    ///
+    /// let description = world.describe(bilbo);
+    /// assert_eq!(description.name, Some("bilbo".into()));
+    /// assert_eq!(description.links, vec![("talking_to".into(), vec![gandalf])]);
+    /// assert_eq!(description.to_string(), format!("\
+    /// {bilbo} \"bilbo\" (hobbit)
+    ///   classes: burglar
+    ///   props:
+    ///     has_ring: true
+    ///     health: 100
+    ///   links:
+    ///     talking_to: {gandalf}
+    /// "));
+    /// ```
+    fn describe(&self, entity: Entity) -> EntityDescription;
+}
+
+impl DescribeExt for World {
+    fn describe(&self, entity: Entity) -> EntityDescription {
+        let entity_ref = self.get_entity(entity).ok();
+        let mut classes: Vec<Estr> = entity_ref
+            .and_then(|entity_ref| entity_ref.get::<Classes>())
+            .into_iter()
+            .flat_map(|classes| classes.iter().copied())
+            .collect();
+        classes.sort_by_key(|class| class.as_str());
+        let mut links: Vec<(Estr, Vec<Entity>)> = self
+            .neighborhood(entity)
+            .into_iter()
+            .map(|(name, targets)| {
+                let mut targets: Vec<Entity> = targets.into_iter().collect();
+                targets.sort();
+                (name, targets)
+            })
+            .collect();
+        links.sort_by_key(|(name, _)| name.as_str());
+        EntityDescription {
+            entity,
+            name: entity_ref.and_then(|entity_ref| entity_ref.get::<Identity>().map(|i| **i)),
+            class: entity_ref.and_then(|entity_ref| entity_ref.get::<Class>().map(|c| **c)),
+            classes,
+            props: entity_ref
+                .and_then(|entity_ref| entity_ref.get::<Props>().cloned())
+                .unwrap_or_default(),
+            links,
+        }
+    }
+}
@@ -6,6 +6,11 @@
 //! + Key-value properties for entities and the world. See [`props`].
 //! + Unique entity names and classes. See [`registry`].
 //! + Arbitrary unidirectional links between entities. See [`links`].
+//! + A plugin to set everything up. See [`PropsPlugin`][plugin::PropsPlugin].
+//! + Saving and loading named entities, with the `serialize` feature. See
+//!   `scene`.
+//! + Deriving conversions between structs and props, with the `derive`
+//!   feature. See [`IntoProps`][props::IntoProps].
 //!
 //! ```
 //! # use bevy_ecs::prelude::*;
@@ -40,13 +45,20 @@
 //! }
 //! ```
 
+pub mod describe;
 pub mod links;
+pub mod plugin;
 pub mod props;
 pub mod registry;
+#[cfg(feature = "serialize")]
+pub mod scene;
 
 #[doc(hidden)]
 pub mod prelude {
+    pub use crate::describe::*;
     pub use crate::links::*;
+    pub use crate::plugin::*;
+    pub use crate::props;
     pub use crate::props::*;
     pub use crate::registry::*;
     pub use estr::Estr;
@@ -1,11 +1,15 @@
 use bevy_ecs::{
+    change_detection::Mut,
     entity::{Entity, EntityHashMap, EntityHashSet},
+    error::ErrorContext,
     system::EntityCommands,
-    world::{EntityMut, EntityRef, EntityWorldMut},
+    world::{EntityMut, EntityRef, EntityWorldMut, World},
 };
-use estr::Estr;
+use bevy_math::Vec3;
+use estr::{Estr, EstrMap};
 
-use super::Links;
+use super::{reindex_links, Links, SharedLinks, SymmetricLinks};
+use crate::registry::RegistryLookupExt;
 
 // -----------------------------------------------------------------------------
 // Immutable links access
@@ -15,47 +19,113 @@ pub trait LinksExt {
 
     fn list_linked(&self, name: impl Into<Estr>) -> EntityHashSet;
 
+    fn list_linked_ref(&self, name: impl Into<Estr>) -> Option<&EntityHashSet>;
+
     fn is_linked(&self, name: impl Into<Estr>, target: Entity) -> bool;
+
+    fn has_link(&self, name: impl Into<Estr>) -> bool;
 }
 
 impl<'w> LinksExt for EntityRef<'w> {
     fn get_linked(&self, name: impl Into<Estr>) -> Option<Entity> {
-        self.get::<Links>()?.get(name)
+        let name = name.into();
+        links_for(self.get(), self.get(), name)?.get(name)
     }
 
     fn list_linked(&self, name: impl Into<Estr>) -> EntityHashSet {
-        match self.get::<Links>() {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
             Some(links) => links.list(name),
             None => EntityHashSet::default(),
         }
     }
 
+    fn list_linked_ref(&self, name: impl Into<Estr>) -> Option<&EntityHashSet> {
+        let name = name.into();
+        links_for(self.get(), self.get(), name)?.list_ref(name)
+    }
+
     fn is_linked(&self, name: impl Into<Estr>, target: Entity) -> bool {
-        match self.get::<Links>() {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
             Some(links) => links.is_linked(name, target),
             None => false,
         }
     }
+
+    fn has_link(&self, name: impl Into<Estr>) -> bool {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
+            Some(links) => links.contains_name(name),
+            None => false,
+        }
+    }
 }
 
 impl<'w> LinksExt for EntityWorldMut<'w> {
     fn get_linked(&self, name: impl Into<Estr>) -> Option<Entity> {
-        self.get::<Links>()?.get(name)
+        let name = name.into();
+        links_for(self.get(), self.get(), name)?.get(name)
     }
 
     fn list_linked(&self, name: impl Into<Estr>) -> EntityHashSet {
-        match self.get::<Links>() {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
             Some(links) => links.list(name),
             None => EntityHashSet::default(),
         }
     }
 
+    fn list_linked_ref(&self, name: impl Into<Estr>) -> Option<&EntityHashSet> {
+        let name = name.into();
+        links_for(self.get(), self.get(), name)?.list_ref(name)
+    }
+
     fn is_linked(&self, name: impl Into<Estr>, target: Entity) -> bool {
-        match self.get::<Links>() {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
             Some(links) => links.is_linked(name, target),
             None => false,
         }
     }
+
+    fn has_link(&self, name: impl Into<Estr>) -> bool {
+        let name = name.into();
+        match links_for(self.get(), self.get(), name) {
+            Some(links) => links.contains_name(name),
+            None => false,
+        }
+    }
+}
+
+/// Returns the table to read a link from: the entity's own [`Links`] if they
+/// override the name, and otherwise its [`SharedLinks`].
+fn links_for<'a>(
+    links: Option<&'a Links>,
+    shared: Option<&'a SharedLinks>,
+    name: Estr,
+) -> Option<&'a Links> {
+    match links {
+        Some(links) if links.overrides(name) => Some(links),
+        _ => shared.map(|shared| &**shared).or(links),
+    }
+}
+
+/// Returns a copy of every link an entity can read, by name, resolving each
+/// name with [`links_for`]. Names with no targets are left out.
+pub(super) fn all_links(
+    links: Option<&Links>,
+    shared: Option<&SharedLinks>,
+) -> EstrMap<EntityHashSet> {
+    [links, shared.map(|shared| &**shared)]
+        .into_iter()
+        .flatten()
+        .flat_map(Links::names)
+        .filter_map(|name| {
+            let targets = links_for(links, shared, name)?.list_ref(name)?;
+            (!targets.is_empty()).then(|| (name, targets.clone()))
+        })
+        .collect()
 }
 
 // -----------------------------------------------------------------------------
@@ -69,37 +139,246 @@ pub trait LinksCommandsExt {
     fn remove_link(&mut self, name: impl Into<Estr>, target: Entity) -> &mut Self;
 
     fn clear_links(&mut self, name: impl Into<Estr>) -> &mut Self;
+
+    fn clear_all_links(&mut self) -> &mut Self;
+
+    fn rename_link(&mut self, from: impl Into<Estr>, to: impl Into<Estr>) -> &mut Self;
+
+    /// Sets a link to the entity registered under `target_name`. If no entity
+    /// has that name, the link is left unchanged and an
+    /// [`EntityNotFoundError`][crate::registry::EntityNotFoundError] is passed to the default error handler.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let gandalf = world.spawn_empty().set_name("gandalf").id();
+    /// let bilbo = world.spawn_empty().set_link_named("talking_to", "gandalf").id();
+    ///
+    /// assert_eq!(world.entity(bilbo).get_linked("talking_to"), Some(gandalf));
+    /// ```
+    fn set_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self;
+
+    /// Adds a link to the entity registered under `target_name`. Errors are
+    /// handled the same way as [`set_link_named`][LinksCommandsExt::set_link_named].
+    fn add_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self;
 }
 
 impl<'w> LinksCommandsExt for EntityWorldMut<'w> {
     fn set_link(&mut self, name: impl Into<Estr>, target: Entity) -> &mut Self {
-        self.entry::<Links>()
-            .or_default()
-            .into_mut()
-            .set(name, target);
+        let name = name.into();
+        if let Some(reverse) = reverse_name(self, name) {
+            for previous in self.list_linked(name) {
+                if previous != target {
+                    remove_back_link(self, reverse, previous);
+                }
+            }
+            set_back_link(self, name, reverse, target);
+        }
+        links_mut(self, name).set(name, target);
+        reindex(self);
         self
     }
 
     fn add_link(&mut self, name: impl Into<Estr>, target: Entity) -> &mut Self {
-        self.entry::<Links>()
-            .or_default()
-            .into_mut()
-            .add(name, target);
+        let name = name.into();
+        if let Some(reverse) = reverse_name(self, name) {
+            add_back_link(self, reverse, target);
+        }
+        links_mut(self, name).add(name, target);
+        reindex(self);
         self
     }
 
     fn remove_link(&mut self, name: impl Into<Estr>, target: Entity) -> &mut Self {
-        self.entry::<Links>()
-            .or_default()
-            .into_mut()
-            .remove(name, target);
+        let name = name.into();
+        if let Some(reverse) = reverse_name(self, name) {
+            remove_back_link(self, reverse, target);
+        }
+        links_mut(self, name).remove(name, target);
+        reindex(self);
         self
     }
 
     fn clear_links(&mut self, name: impl Into<Estr>) -> &mut Self {
-        self.entry::<Links>().or_default().into_mut().clear(name);
+        let name = name.into();
+        if let Some(reverse) = reverse_name(self, name) {
+            for previous in self.list_linked(name) {
+                remove_back_link(self, reverse, previous);
+            }
+        }
+        links_mut(self, name).clear(name);
+        reindex(self);
         self
     }
+
+    fn clear_all_links(&mut self) -> &mut Self {
+        let names: Vec<Estr> = [self.get::<Links>(), self.get::<SharedLinks>().map(|s| &**s)]
+            .into_iter()
+            .flatten()
+            .flat_map(Links::names)
+            .collect();
+        for name in names {
+            self.clear_links(name);
+        }
+        self.entry::<Links>().or_default().into_mut().clear_all();
+        self.remove::<SharedLinks>();
+        reindex(self);
+        self
+    }
+
+    fn rename_link(&mut self, from: impl Into<Estr>, to: impl Into<Estr>) -> &mut Self {
+        let from = from.into();
+        let to = to.into();
+        if from == to {
+            return self;
+        }
+        let targets = self.list_linked(from);
+        if let Some(reverse) = reverse_name(self, from) {
+            for &target in &targets {
+                remove_back_link(self, reverse, target);
+            }
+        }
+        if let Some(reverse) = reverse_name(self, to) {
+            for &target in &targets {
+                add_back_link(self, reverse, target);
+            }
+        }
+        if self.contains::<SharedLinks>() {
+            links_mut(self, to);
+            let mut links = links_mut(self, from);
+            links.rename(from, to);
+            // Leave an empty entry behind to keep hiding the shared link
+            links.clear(from);
+        } else if let Some(mut links) = self.get_mut::<Links>() {
+            links.rename(from, to);
+        }
+        reindex(self);
+        self
+    }
+
+    fn set_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self {
+        match lookup_target(self, target_name.into(), "set_link_named") {
+            Some(target) => self.set_link(name, target),
+            None => self,
+        }
+    }
+
+    fn add_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self {
+        match lookup_target(self, target_name.into(), "add_link_named") {
+            Some(target) => self.add_link(name, target),
+            None => self,
+        }
+    }
+}
+
+/// Returns an entity's [`Links`] for changing a link, first copying the link's
+/// targets from its [`SharedLinks`] if the link is not already overridden.
+fn links_mut<'a>(entity: &'a mut EntityWorldMut, name: Estr) -> Mut<'a, Links> {
+    let copied = match (entity.get::<Links>(), entity.get::<SharedLinks>()) {
+        (Some(links), _) if links.overrides(name) => None,
+        (_, Some(shared)) => shared.links.get(&name).cloned(),
+        _ => None,
+    };
+    let mut links = entity.entry::<Links>().or_default().into_mut();
+    if let Some(targets) = copied {
+        links.links.insert(name, targets);
+    }
+    links
+}
+
+/// Updates the [`LinkIndex`][super::LinkIndex] after changing an entity's
+/// links, so the change is indexed straight away.
+fn reindex(entity: &mut EntityWorldMut) {
+    let source = entity.id();
+    entity.world_scope(|world| reindex_links(world, source));
+}
+
+/// Looks up a link target by name, reporting an error if it is not registered.
+fn lookup_target(entity: &EntityWorldMut, name: Estr, command: &'static str) -> Option<Entity> {
+    let world = entity.world();
+    match world.lookup_name(name) {
+        Ok(target) => Some(target),
+        Err(error) => {
+            let error_handler = world.default_error_handler();
+            error_handler(
+                error.into(),
+                ErrorContext::Command {
+                    name: command.into(),
+                },
+            );
+            None
+        }
+    }
+}
+
+/// Returns the reverse name of a symmetric link, if one is declared.
+fn reverse_name(entity: &EntityWorldMut, name: Estr) -> Option<Estr> {
+    entity
+        .world()
+        .get_resource::<SymmetricLinks>()?
+        .reverse(name)
+}
+
+/// Adds a link from `target` back to `entity`.
+fn add_back_link(entity: &mut EntityWorldMut, reverse: Estr, target: Entity) {
+    let source = entity.id();
+    entity.world_scope(|world| {
+        if let Ok(mut target_mut) = world.get_entity_mut(target) {
+            target_mut
+                .entry::<Links>()
+                .or_default()
+                .into_mut()
+                .add(reverse, source);
+            reindex_links(world, target);
+        }
+    });
+}
+
+/// Sets the link from `target` back to `entity`, replacing its other targets.
+/// Each replaced target loses its own `name` link to `target`, so both sides
+/// of the symmetric link stay in sync.
+fn set_back_link(entity: &mut EntityWorldMut, name: Estr, reverse: Estr, target: Entity) {
+    let source = entity.id();
+    entity.world_scope(|world| {
+        let Ok(mut target_mut) = world.get_entity_mut(target) else {
+            return;
+        };
+        let mut links = target_mut.entry::<Links>().or_default().into_mut();
+        let displaced: Vec<Entity> = links
+            .list(reverse)
+            .into_iter()
+            .filter(|&previous| previous != source)
+            .collect();
+        links.set(reverse, source);
+        reindex_links(world, target);
+        for previous in displaced {
+            if let Some(mut links) = world
+                .get_entity_mut(previous)
+                .ok()
+                .and_then(|previous| previous.into_mut::<Links>())
+            {
+                links.remove(name, target);
+                reindex_links(world, previous);
+            }
+        }
+    });
+}
+
+/// Removes the link from `target` back to `entity`.
+fn remove_back_link(entity: &mut EntityWorldMut, reverse: Estr, target: Entity) {
+    let source = entity.id();
+    entity.world_scope(|world| {
+        if let Some(mut links) = world
+            .get_entity_mut(target)
+            .ok()
+            .and_then(|target| target.into_mut::<Links>())
+        {
+            links.remove(reverse, source);
+            reindex_links(world, target);
+        }
+    });
 }
 
 impl<'a> LinksCommandsExt for EntityCommands<'a> {
@@ -130,6 +409,36 @@ impl<'a> LinksCommandsExt for EntityCommands<'a> {
             entity.clear_links(name);
         })
     }
+
+    fn clear_all_links(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            entity.clear_all_links();
+        })
+    }
+
+    fn rename_link(&mut self, from: impl Into<Estr>, to: impl Into<Estr>) -> &mut Self {
+        let from = from.into();
+        let to = to.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.rename_link(from, to);
+        })
+    }
+
+    fn set_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self {
+        let name = name.into();
+        let target_name = target_name.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_link_named(name, target_name);
+        })
+    }
+
+    fn add_link_named(&mut self, name: impl Into<Estr>, target_name: impl Into<Estr>) -> &mut Self {
+        let name = name.into();
+        let target_name = target_name.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_link_named(name, target_name);
+        })
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -151,9 +460,419 @@ impl<'w> LinksFollowExt<'w> for EntityWorldMut<'w> {
     fn explore_link(self, name: impl Into<Estr>) -> EntityHashMap<EntityMut<'w>> {
         let targets = self.list_linked(name);
         let world = self.into_world_mut();
-        match world.get_entity_mut(&targets) {
-            Ok(entities) => entities,
-            Err(_) => EntityHashMap::default(),
+        world.get_entity_mut(&targets).unwrap_or_default()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// World-level links access
+
+/// Adds queries over the links of every entity in a [`World`].
+pub trait LinksWorldExt {
+    /// Returns every entity that links to `target` under the given name.
+    ///
+    /// This checks the [`Links`] component of every entity, and so costs
+    /// `O(n)` in the number of entities with links. The
+    /// [`LinkIndex`][super::LinkIndex] resource answers the same question
+    /// without a scan, and includes [`SharedLinks`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let gandalf = world.spawn_empty().id();
+    /// let bilbo = world.spawn_empty().set_link("talking_to", gandalf).id();
+    ///
+    /// assert!(world.incoming_links(gandalf, "talking_to").contains(&bilbo));
+    /// ```
+    fn incoming_links(&self, target: Entity, name: impl Into<Estr>) -> EntityHashSet;
+
+    /// Returns the targets of a link which satisfy a predicate. Targets that
+    /// no longer exist are skipped.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let orc = world.spawn_empty().set_prop("health", 10.0).id();
+    /// let troll = world.spawn_empty().set_prop("health", 80.0).id();
+    /// let aragorn = world.spawn_empty()
+    ///     .add_link("visible", orc)
+    ///     .add_link("visible", troll)
+    ///     .id();
+    ///
+    /// let weak = world.list_linked_where(aragorn, "visible", |target| {
+    ///     target.get_prop::<f32>("health") < 20.0
+    /// });
+    /// assert_eq!(weak, vec![orc]);
+    /// ```
+    ///
+    /// Links read from [`SharedLinks`] are included, unless the entity's own
+    /// [`Links`] override them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let orc = world.spawn_empty().set_prop("health", 10.0).id();
+    /// let mut table = Links::default();
+    /// table.add("visible", orc);
+    /// let aragorn = world.spawn(SharedLinks::new(table)).id();
+    ///
+    /// let weak = world.list_linked_where(aragorn, "visible", |target| {
+    ///     target.get_prop::<f32>("health") < 20.0
+    /// });
+    /// assert_eq!(weak, vec![orc]);
+    /// ```
+    fn list_linked_where(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+        predicate: impl Fn(EntityRef) -> bool,
+    ) -> Vec<Entity>;
+
+    /// Returns the target of a link closest to the source entity, or `None`
+    /// if the link has no targets that still exist. Positions are supplied by
+    /// the caller, so this works with any notion of position.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::Vec3;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let wolf = world.spawn_empty().set_prop("x", 10.0).id();
+    /// let bear = world.spawn_empty().set_prop("x", 3.0).id();
+    /// let ranger = world.spawn_empty()
+    ///     .add_link("aware_of", wolf)
+    ///     .add_link("aware_of", bear)
+    ///     .id();
+    ///
+    /// let nearest = world.nearest_linked(ranger, "aware_of", |entity| {
+    ///     Vec3::new(world.entity(entity).get_prop("x"), 0.0, 0.0)
+    /// });
+    /// assert_eq!(nearest, Some(bear));
+    /// ```
+    ///
+    /// Links read from [`SharedLinks`] are included, unless the entity's own
+    /// [`Links`] override them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::Vec3;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let wolf = world.spawn_empty().set_prop("x", 10.0).id();
+    /// let bear = world.spawn_empty().set_prop("x", 3.0).id();
+    /// let mut table = Links::default();
+    /// table.add("aware_of", wolf);
+    /// table.add("aware_of", bear);
+    /// let ranger = world.spawn(SharedLinks::new(table)).id();
+    ///
+    /// let nearest = world.nearest_linked(ranger, "aware_of", |entity| {
+    ///     Vec3::new(world.entity(entity).get_prop("x"), 0.0, 0.0)
+    /// });
+    /// assert_eq!(nearest, Some(bear));
+    /// ```
+    fn nearest_linked(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+        position_of: impl Fn(Entity) -> Vec3,
+    ) -> Option<Entity>;
+
+    /// Splits the graph formed by a link into strongly connected components:
+    /// groups of entities which can all reach each other by following the
+    /// link. Every entity in the graph (every entity with the link, and every
+    /// living target) appears in exactly one component, so entities which are
+    /// not part of a cycle form components of their own.
+    ///
+    /// The result is deterministic: each component is sorted, and the
+    /// components are sorted by their first entity.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let [a, b, c, d] = [(); 4].map(|_| world.spawn_empty().id());
+    /// world.entity_mut(a).add_link("allied_with", b);
+    /// world.entity_mut(b).add_link("allied_with", a);
+    /// world.entity_mut(b).add_link("allied_with", c);
+    /// world.entity_mut(c).add_link("allied_with", c);
+    /// world.entity_mut(c).add_link("allied_with", d);
+    ///
+    /// let components = world.strongly_connected_components("allied_with");
+    /// assert_eq!(components.len(), 3);
+    /// assert!(components.iter().any(|c| c.len() == 2 && c.contains(&a) && c.contains(&b)));
+    /// assert!(components.contains(&vec![c]));
+    /// assert!(components.contains(&vec![d]));
+    /// ```
+    fn strongly_connected_components(&self, name: impl Into<Estr>) -> Vec<Vec<Entity>>;
+
+    /// Returns the target of a link from the source entity, if it still
+    /// exists. If the link points to multiple entities, any of them may be
+    /// returned.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let gandalf = world.spawn_empty().set_prop("likes_elves", true).id();
+    /// let bilbo = world.spawn_empty().set_link("talking_to", gandalf).id();
+    ///
+    /// let target = world.follow_link(bilbo, "talking_to").unwrap();
+    /// assert!(target.get_prop::<bool>("likes_elves"));
+    /// ```
+    fn follow_link(&self, source: Entity, name: impl Into<Estr>) -> Option<EntityRef<'_>>;
+
+    /// Creates an iterator over every target of a link from the source entity
+    /// which still exists.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let orc = world.spawn_empty().set_prop("health", 10.0).id();
+    /// let troll = world.spawn_empty().set_prop("health", 80.0).id();
+    /// let aragorn = world.spawn_empty()
+    ///     .add_link("visible", orc)
+    ///     .add_link("visible", troll)
+    ///     .id();
+    ///
+    /// let total: f32 = world.follow_links(aragorn, "visible")
+    ///     .map(|target| target.get_prop::<f32>("health"))
+    ///     .sum();
+    /// assert_eq!(total, 90.0);
+    /// ```
+    fn follow_links(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+    ) -> impl Iterator<Item = EntityRef<'_>>;
+
+    /// Follows several links from the source entity at once, returning the
+    /// target of each which still exists. See
+    /// [`follow_link`][LinksWorldExt::follow_link].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let helm = world.spawn_empty().set_prop("armor", 2.0).id();
+    /// let mail = world.spawn_empty().set_prop("armor", 5.0).id();
+    /// let knight = world.spawn_empty()
+    ///     .set_link("head", helm)
+    ///     .set_link("torso", mail)
+    ///     .id();
+    ///
+    /// let armor: f32 = world.follow_many(knight, ["head", "torso", "legs"])
+    ///     .into_iter()
+    ///     .flatten()
+    ///     .map(|piece| piece.get_prop::<f32>("armor"))
+    ///     .sum();
+    /// assert_eq!(armor, 7.0);
+    /// ```
+    fn follow_many<S: Into<Estr>, const N: usize>(
+        &self,
+        source: Entity,
+        names: [S; N],
+    ) -> [Option<EntityRef<'_>>; N];
+
+    /// Returns a copy of every link from an entity, by name, including links
+    /// read from its [`SharedLinks`]. Names with no targets are left out.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let gandalf = world.spawn_empty().id();
+    /// let orc = world.spawn_empty().id();
+    /// let bilbo = world.spawn_empty()
+    ///     .set_link("talking_to", gandalf)
+    ///     .add_link("fleeing", orc)
+    ///     .add_link("fleeing", gandalf)
+    ///     .id();
+    ///
+    /// let neighborhood = world.neighborhood(bilbo);
+    /// assert_eq!(neighborhood.len(), 2);
+    /// assert!(neighborhood[&Estr::from("talking_to")].contains(&gandalf));
+    /// assert_eq!(neighborhood[&Estr::from("fleeing")].len(), 2);
+    /// ```
+    fn neighborhood(&self, entity: Entity) -> EstrMap<EntityHashSet>;
+}
+
+impl LinksWorldExt for World {
+    fn incoming_links(&self, target: Entity, name: impl Into<Estr>) -> EntityHashSet {
+        let name = name.into();
+        let Some(mut query) = self.try_query::<(Entity, &Links)>() else {
+            return EntityHashSet::default();
+        };
+        query
+            .iter(self)
+            .filter(|(_, links)| links.is_linked(name, target))
+            .map(|(source, _)| source)
+            .collect()
+    }
+
+    fn list_linked_where(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+        predicate: impl Fn(EntityRef) -> bool,
+    ) -> Vec<Entity> {
+        let name = name.into();
+        let Ok(source) = self.get_entity(source) else {
+            return Vec::new();
+        };
+        let Some(targets) =
+            links_for(source.get(), source.get(), name).and_then(|links| links.list_ref(name))
+        else {
+            return Vec::new();
+        };
+        targets
+            .iter()
+            .filter_map(|&target| self.get_entity(target).ok())
+            .filter(|&target| predicate(target))
+            .map(|target| target.id())
+            .collect()
+    }
+
+    fn nearest_linked(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+        position_of: impl Fn(Entity) -> Vec3,
+    ) -> Option<Entity> {
+        let name = name.into();
+        let entity = self.get_entity(source).ok()?;
+        let targets = links_for(entity.get(), entity.get(), name)?.list_ref(name)?;
+        let origin = position_of(source);
+        targets
+            .iter()
+            .copied()
+            .filter(|&target| self.entities().contains_spawned(target))
+            .map(|target| (target, origin.distance_squared(position_of(target))))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(target, _)| target)
+    }
+
+    fn strongly_connected_components(&self, name: impl Into<Estr>) -> Vec<Vec<Entity>> {
+        let name = name.into();
+        let Some(mut query) = self.try_query::<(Entity, &Links)>() else {
+            return Vec::new();
+        };
+
+        // Build the adjacency list, skipping dead targets
+        let mut graph: EntityHashMap<Vec<Entity>> = EntityHashMap::default();
+        for (source, links) in query.iter(self) {
+            let Some(targets) = links.links.get(&name) else {
+                continue;
+            };
+            let mut targets: Vec<Entity> = targets
+                .iter()
+                .copied()
+                .filter(|&target| self.entities().contains_spawned(target))
+                .collect();
+            targets.sort();
+            for &target in &targets {
+                graph.entry(target).or_default();
+            }
+            graph.entry(source).or_default().extend(targets);
+        }
+        let mut nodes: Vec<Entity> = graph.keys().copied().collect();
+        nodes.sort();
+
+        // Tarjan's algorithm, with an explicit stack in place of recursion
+        let mut next_index = 0;
+        let mut index = EntityHashMap::<usize>::default();
+        let mut lowlink = EntityHashMap::<usize>::default();
+        let mut stack = Vec::new();
+        let mut on_stack = EntityHashSet::default();
+        let mut components = Vec::new();
+        for root in nodes {
+            if index.contains_key(&root) {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            index.insert(root, next_index);
+            lowlink.insert(root, next_index);
+            next_index += 1;
+            stack.push(root);
+            on_stack.insert(root);
+            while let Some((node, edge)) = calls.last_mut() {
+                let node = *node;
+                if let Some(&target) = graph[&node].get(*edge) {
+                    *edge += 1;
+                    if !index.contains_key(&target) {
+                        index.insert(target, next_index);
+                        lowlink.insert(target, next_index);
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack.insert(target);
+                        calls.push((target, 0));
+                    } else if on_stack.contains(&target) {
+                        let low = lowlink[&node].min(index[&target]);
+                        lowlink.insert(node, low);
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    let low = lowlink[&parent].min(lowlink[&node]);
+                    lowlink.insert(parent, low);
+                }
+                if lowlink[&node] == index[&node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+            }
         }
+        components.sort();
+        components
+    }
+
+    fn follow_link(&self, source: Entity, name: impl Into<Estr>) -> Option<EntityRef<'_>> {
+        let target = self.get_entity(source).ok()?.get_linked(name)?;
+        self.get_entity(target).ok()
+    }
+
+    fn follow_links(
+        &self,
+        source: Entity,
+        name: impl Into<Estr>,
+    ) -> impl Iterator<Item = EntityRef<'_>> {
+        let targets = match self.get_entity(source) {
+            Ok(source) => source.list_linked(name),
+            Err(_) => EntityHashSet::default(),
+        };
+        targets
+            .into_iter()
+            .filter_map(|target| self.get_entity(target).ok())
+    }
+
+    fn follow_many<S: Into<Estr>, const N: usize>(
+        &self,
+        source: Entity,
+        names: [S; N],
+    ) -> [Option<EntityRef<'_>>; N] {
+        let source = self.get_entity(source).ok();
+        names.map(|name| {
+            let target = source?.get_linked(name)?;
+            self.get_entity(target).ok()
+        })
+    }
+
+    fn neighborhood(&self, entity: Entity) -> EstrMap<EntityHashSet> {
+        let Ok(entity) = self.get_entity(entity) else {
+            return EstrMap::default();
+        };
+        all_links(entity.get(), entity.get())
     }
 }
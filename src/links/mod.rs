@@ -15,13 +15,33 @@
 //! }
 //! ```
 
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "reflect")]
+use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
 use bevy_ecs::{
     component::Component,
-    entity::{Entity, EntityHashSet},
+    entity::{Entity, EntityHashMap, EntityHashSet, EntityMapper, MapEntities},
+    lifecycle::{Despawn, HookContext},
+    observer::On,
+    query::{Changed, Or},
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
+    world::{DeferredWorld, World},
 };
+#[cfg(feature = "reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "reflect", feature = "serialize"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use estr::{Estr, EstrMap};
 
 mod ext;
+#[cfg(feature = "serialize")]
+mod serialize;
+use ext::all_links;
 pub use ext::*;
 
 /// Stores links between entities.
@@ -33,35 +53,179 @@ pub use ext::*;
 ///
 /// To create one-to-one links, use [`set`][Links::set] and [`get`][Links::get]. You can also create
 /// many-to-one or many-to-many links using [`add`][Links::add] and [`list`][Links::list].
-#[derive(Component, Default)]
+///
+/// Links implement [`MapEntities`], so link targets are remapped when links
+/// are loaded as part of a scene. With the `serialize` feature, links can also
+/// be serialized with serde.
+#[derive(Component, Default, Clone)]
+#[component(map_entities)]
+#[component(on_insert = index_links)]
+#[component(on_replace = unindex_links)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Default, Clone, MapEntities)
+)]
+#[cfg_attr(
+    all(feature = "reflect", feature = "serialize"),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Links {
     links: EstrMap<EntityHashSet>,
+    /// The order targets were added with [`add_lru`][Links::add_lru], oldest
+    /// first. This may contain targets which have since been removed.
+    lru_order: EstrMap<VecDeque<Entity>>,
+    /// Weights of targets added with [`add_weighted`][Links::add_weighted].
+    /// Targets without an entry have a weight of `1.0`.
+    weights: EstrMap<EntityHashMap<f32>>,
 }
 
 impl Links {
     /// Sets a link to a specific entity. The previous value of this link will be overwritten.
     pub fn set(&mut self, name: impl Into<Estr>, target: Entity) {
-        let link = self.links.entry(name.into()).or_default();
+        let name = name.into();
+        let link = self.links.entry(name).or_default();
         link.clear();
         link.insert(target);
+        self.weights.remove(&name);
     }
 
     /// Adds a link to a specific entity. The same link can point to multiple entities.
     pub fn add(&mut self, name: impl Into<Estr>, target: Entity) {
-        let link = self.links.entry(name.into()).or_default();
+        let name = name.into();
+        let link = self.links.entry(name).or_default();
+        link.insert(target);
+        self.unweight(name, target);
+    }
+
+    /// Adds a link to a specific entity, keeping at most `capacity` targets
+    /// under this name. When there are too many, the least recently added
+    /// targets are evicted, and the oldest one evicted is returned. Adding a
+    /// target which is already linked makes it the most recent.
+    ///
+    /// Only `add_lru` records the order targets were added in. Targets linked
+    /// some other way (including by loading saved links) count as older than
+    /// any added with `add_lru`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let [a, b, c, d] = [1, 2, 3, 4].map(|i| Entity::from_raw_u32(i).unwrap());
+    /// let mut links = Links::default();
+    /// assert_eq!(links.add_lru("recent", a, 3), None);
+    /// assert_eq!(links.add_lru("recent", b, 3), None);
+    /// assert_eq!(links.add_lru("recent", c, 3), None);
+    ///
+    /// assert_eq!(links.add_lru("recent", d, 3), Some(a));
+    /// assert!(!links.is_linked("recent", a));
+    ///
+    /// // re-adding `b` makes `c` the oldest
+    /// links.add_lru("recent", b, 3);
+    /// assert_eq!(links.add_lru("recent", a, 3), Some(c));
+    /// assert_eq!(links.count("recent"), 3);
+    /// ```
+    pub fn add_lru(
+        &mut self,
+        name: impl Into<Estr>,
+        target: Entity,
+        capacity: usize,
+    ) -> Option<Entity> {
+        let name = name.into();
+        let link = self.links.entry(name).or_default();
+        let order = self.lru_order.entry(name).or_default();
+        order.retain(|&entity| entity != target && link.contains(&entity));
+        let untracked: Vec<Entity> = link
+            .iter()
+            .copied()
+            .filter(|&entity| entity != target && !order.contains(&entity))
+            .collect();
+        for entity in untracked {
+            order.push_front(entity);
+        }
         link.insert(target);
+        order.push_back(target);
+        if let Some(weights) = self.weights.get_mut(&name) {
+            weights.remove(&target);
+        }
+
+        let mut evicted = None;
+        while link.len() > capacity
+            && let Some(oldest) = order.pop_front()
+        {
+            link.remove(&oldest);
+            if let Some(weights) = self.weights.get_mut(&name) {
+                weights.remove(&oldest);
+            }
+            evicted = evicted.or(Some(oldest));
+        }
+        evicted
     }
 
     /// Removes an entity from a link.
     pub fn remove(&mut self, name: impl Into<Estr>, target: Entity) {
-        let link = self.links.entry(name.into()).or_default();
+        let name = name.into();
+        let link = self.links.entry(name).or_default();
         link.remove(&target);
+        self.unweight(name, target);
     }
 
     /// Clears the value of a link.
     pub fn clear(&mut self, name: impl Into<Estr>) {
-        let link = self.links.entry(name.into()).or_default();
+        let name = name.into();
+        let link = self.links.entry(name).or_default();
         link.clear();
+        self.weights.remove(&name);
+    }
+
+    /// Moves all targets of one link name to another, merging them into any
+    /// targets the destination name already has.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let troll = Entity::from_raw_u32(1).unwrap();
+    /// # let goblin = Entity::from_raw_u32(2).unwrap();
+    /// let mut links = Links::default();
+    /// links.add("enemy", troll);
+    /// links.add("hostile", goblin);
+    /// links.rename("enemy", "hostile");
+    ///
+    /// assert!(!links.contains_name("enemy"));
+    /// assert_eq!(links.count("hostile"), 2);
+    /// ```
+    pub fn rename(&mut self, from: impl Into<Estr>, to: impl Into<Estr>) {
+        let from = from.into();
+        let to = to.into();
+        if from == to {
+            return;
+        }
+        if let Some(targets) = self.links.remove(&from) {
+            self.links.entry(to).or_default().extend(targets);
+        }
+        self.lru_order.remove(&from);
+        if let Some(weights) = self.weights.remove(&from) {
+            self.weights.entry(to).or_default().extend(weights);
+        }
+    }
+
+    /// Clears every link.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let troll = Entity::PLACEHOLDER;
+    /// let mut links = Links::default();
+    /// links.set("looking_at", troll);
+    /// links.add("enemies", troll);
+    /// links.clear_all();
+    ///
+    /// assert_eq!(links.get("looking_at"), None);
+    /// assert!(links.list("enemies").is_empty());
+    /// ```
+    pub fn clear_all(&mut self) {
+        self.links.clear();
+        self.lru_order.clear();
+        self.weights.clear();
     }
 
     /// Returns true if the entity is linked under this name.
@@ -73,6 +237,19 @@ impl Links {
         }
     }
 
+    /// Returns true if at least one entity is linked under this name.
+    pub fn contains_name(&self, name: impl Into<Estr>) -> bool {
+        self.links
+            .get(&name.into())
+            .is_some_and(|link| !link.is_empty())
+    }
+
+    /// Returns true if this has an entry for the name, even an empty one.
+    /// Entries shadow the same name in [`SharedLinks`].
+    pub(crate) fn overrides(&self, name: Estr) -> bool {
+        self.links.contains_key(&name)
+    }
+
     /// Returns the linked entity. If the link points to multiple entities,
     /// any of them may be returned (which is explicetly left undefined).
     pub fn get(&self, name: impl Into<Estr>) -> Option<Entity> {
@@ -82,6 +259,24 @@ impl Links {
             .copied()
     }
 
+    /// Returns the linked entity for each of several names, in order. See
+    /// [`get`][Links::get].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let [helm, mail] = [1, 2].map(|i| Entity::from_raw_u32(i).unwrap());
+    /// let mut links = Links::default();
+    /// links.set("head", helm);
+    /// links.set("torso", mail);
+    ///
+    /// let [head, torso, legs] = links.get_many(["head", "torso", "legs"]);
+    /// assert_eq!((head, torso, legs), (Some(helm), Some(mail), None));
+    /// ```
+    pub fn get_many<S: Into<Estr>, const N: usize>(&self, names: [S; N]) -> [Option<Entity>; N] {
+        names.map(|name| self.get(name))
+    }
+
     /// Returns all linked entities. If the link points to multiple entities,
     /// all will be returned.
     pub fn list(&self, name: impl Into<Estr>) -> EntityHashSet {
@@ -90,4 +285,1009 @@ impl Links {
             .cloned()
             .unwrap_or(EntityHashSet::new())
     }
+
+    /// Returns all linked entities without cloning them, or `None` if nothing
+    /// is linked under this name. Prefer this to [`list`][Links::list] when
+    /// the targets only need to be read.
+    ///
+    /// ```
+    /// # use bevy_ecs::entity::Entity;
+    /// # use bevy_mod_props::prelude::*;
+    /// let target = Entity::from_raw_u32(1).unwrap();
+    /// let mut links = Links::default();
+    /// links.add("friend", target);
+    ///
+    /// assert!(links.list_ref("friend").unwrap().contains(&target));
+    /// assert!(links.list_ref("enemy").is_none());
+    /// ```
+    pub fn list_ref(&self, name: impl Into<Estr>) -> Option<&EntityHashSet> {
+        self.links.get(&name.into())
+    }
+
+    /// Returns the number of entities linked under this name.
+    pub fn count(&self, name: impl Into<Estr>) -> usize {
+        self.links.get(&name.into()).map_or(0, EntityHashSet::len)
+    }
+
+    /// Returns true if no entities are linked under this name.
+    pub fn is_empty(&self, name: impl Into<Estr>) -> bool {
+        self.count(name) == 0
+    }
+
+    /// Returns the number of distinct link names with at least one target.
+    pub fn link_count(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Creates a borrowing iterator over the names of all links with at least
+    /// one target.
+    pub fn names(&self) -> impl Iterator<Item = Estr> + '_ {
+        self.iter().map(|(name, _)| name)
+    }
+
+    /// Creates a borrowing iterator over the names and targets of all links
+    /// with at least one target.
+    pub fn iter(&self) -> impl Iterator<Item = (Estr, &EntityHashSet)> {
+        self.links
+            .iter()
+            .filter(|(_, targets)| !targets.is_empty())
+            .map(|(&name, targets)| (name, targets))
+    }
+
+    /// Creates a borrowing iterator over every individual link, as a pair of
+    /// a link name and a single target, in no particular order.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let (a, b) = (world.spawn_empty().id(), world.spawn_empty().id());
+    /// let mut links = Links::default();
+    /// links.add("friends", a);
+    /// links.add("friends", b);
+    /// links.set("leader", a);
+    ///
+    /// let edges: Vec<_> = links.edges().collect();
+    /// assert_eq!(edges.len(), 3);
+    /// assert!(edges.contains(&("friends".into(), a)));
+    /// assert!(edges.contains(&("friends".into(), b)));
+    /// assert!(edges.contains(&("leader".into(), a)));
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = (Estr, Entity)> + '_ {
+        self.iter()
+            .flat_map(|(name, targets)| targets.iter().map(move |&target| (name, target)))
+    }
+}
+
+impl MapEntities for Links {
+    /// Remaps the targets of every link.
+    ///
+    /// ```
+    /// # use bevy_ecs::entity::{EntityHashMap, MapEntities};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let saved = Entity::from_raw_u32(1).unwrap();
+    /// # let loaded = Entity::from_raw_u32(2).unwrap();
+    /// let mut links = Links::default();
+    /// links.set("talking_to", saved);
+    ///
+    /// let mut entity_map = EntityHashMap::default();
+    /// entity_map.insert(saved, loaded);
+    /// links.map_entities(&mut entity_map);
+    ///
+    /// assert_eq!(links.get("talking_to"), Some(loaded));
+    /// ```
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        for targets in self.links.values_mut() {
+            *targets = targets
+                .drain()
+                .map(|target| entity_mapper.get_mapped(target))
+                .collect();
+        }
+        for order in self.lru_order.values_mut() {
+            for target in order.iter_mut() {
+                *target = entity_mapper.get_mapped(*target);
+            }
+        }
+        for weights in self.weights.values_mut() {
+            *weights = weights
+                .drain()
+                .map(|(target, weight)| (entity_mapper.get_mapped(target), weight))
+                .collect();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Weights
+
+impl Links {
+    /// Adds a link to a specific entity with a weight, such as the cost of a
+    /// path or the strength of a relationship. Adding a target that is
+    /// already linked updates its weight.
+    ///
+    /// Targets linked without a weight, with [`add`][Links::add] or
+    /// [`set`][Links::set], have a weight of `1.0`, and linking a target
+    /// that way resets its weight. Removing or clearing a target forgets its
+    /// weight. Weights are not included in diffs or in serialized links.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let [town, cave, river] = [1, 2, 3].map(|i| Entity::from_raw_u32(i).unwrap());
+    /// let mut links = Links::default();
+    /// links.add_weighted("path_to", town, 4.0);
+    /// links.add_weighted("path_to", cave, 9.5);
+    /// links.add("path_to", river);
+    ///
+    /// assert_eq!(links.weight("path_to", cave), Some(9.5));
+    /// assert_eq!(links.weight("path_to", river), Some(1.0));
+    ///
+    /// let mut paths: Vec<_> = links.list_weighted("path_to").collect();
+    /// paths.sort_by(|a, b| a.1.total_cmp(&b.1));
+    /// assert_eq!(paths, vec![(river, 1.0), (town, 4.0), (cave, 9.5)]);
+    ///
+    /// links.remove("path_to", cave);
+    /// assert_eq!(links.weight("path_to", cave), None);
+    /// ```
+    pub fn add_weighted(&mut self, name: impl Into<Estr>, target: Entity, weight: f32) {
+        let name = name.into();
+        self.links.entry(name).or_default().insert(target);
+        self.weights.entry(name).or_default().insert(target, weight);
+    }
+
+    /// Returns the weight of a linked entity, or `None` if it is not linked
+    /// under this name.
+    pub fn weight(&self, name: impl Into<Estr>, target: Entity) -> Option<f32> {
+        let name = name.into();
+        if !self.is_linked(name, target) {
+            return None;
+        }
+        let weight = self
+            .weights
+            .get(&name)
+            .and_then(|weights| weights.get(&target));
+        Some(weight.copied().unwrap_or(1.0))
+    }
+
+    /// Creates an iterator over every entity linked under this name and its
+    /// weight, in no particular order.
+    pub fn list_weighted(&self, name: impl Into<Estr>) -> impl Iterator<Item = (Entity, f32)> + '_ {
+        let name = name.into();
+        let weights = self.weights.get(&name);
+        self.links
+            .get(&name)
+            .into_iter()
+            .flatten()
+            .map(move |&target| {
+                let weight = weights.and_then(|weights| weights.get(&target));
+                (target, weight.copied().unwrap_or(1.0))
+            })
+    }
+
+    /// Forgets the weight of a target, so it has the default weight.
+    fn unweight(&mut self, name: Estr, target: Entity) {
+        if let Some(weights) = self.weights.get_mut(&name) {
+            weights.remove(&target);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Diffs
+
+/// The difference between two sets of links, per link name. Created with
+/// [`Links::diff`], and applied with [`Links::apply_diff`].
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct LinksDiff {
+    /// Targets which are added, by link name.
+    pub added: EstrMap<EntityHashSet>,
+    /// Targets which are removed, by link name.
+    pub removed: EstrMap<EntityHashSet>,
+}
+
+impl LinksDiff {
+    /// Returns true if the diff makes no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Links {
+    /// Returns the changes which turn these links into `other`. Applying the
+    /// diff to a copy of these links with [`apply_diff`][Links::apply_diff]
+    /// produces links equal to `other`, which is useful for undo and for
+    /// replicating links over the network.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # let [troll, goblin, orc] = [1, 2, 3].map(|i| Entity::from_raw_u32(i).unwrap());
+    /// let mut before = Links::default();
+    /// before.add("enemies", troll);
+    /// before.add("enemies", goblin);
+    /// before.set("looking_at", troll);
+    ///
+    /// let mut after = Links::default();
+    /// after.add("enemies", goblin);
+    /// after.add("enemies", orc);
+    /// after.set("following", goblin);
+    ///
+    /// let diff = before.diff(&after);
+    /// assert!(diff.added[&Estr::from("enemies")].contains(&orc));
+    /// assert!(diff.removed[&Estr::from("enemies")].contains(&troll));
+    ///
+    /// before.apply_diff(&diff);
+    /// assert!(before.diff(&after).is_empty());
+    /// assert_eq!(before.get("looking_at"), None);
+    /// assert_eq!(before.get("following"), Some(goblin));
+    /// ```
+    pub fn diff(&self, other: &Links) -> LinksDiff {
+        LinksDiff {
+            added: difference(&other.links, &self.links),
+            removed: difference(&self.links, &other.links),
+        }
+    }
+
+    /// Applies the changes from a [`LinksDiff`].
+    pub fn apply_diff(&mut self, diff: &LinksDiff) {
+        for (&name, targets) in &diff.removed {
+            if let Some(link) = self.links.get_mut(&name) {
+                for target in targets {
+                    link.remove(target);
+                }
+            }
+            if let Some(weights) = self.weights.get_mut(&name) {
+                for target in targets {
+                    weights.remove(target);
+                }
+            }
+        }
+        for (&name, targets) in &diff.added {
+            self.links.entry(name).or_default().extend(targets);
+        }
+    }
+}
+
+/// Returns the targets in `a` which are not in `b`, for each link name.
+fn difference(a: &EstrMap<EntityHashSet>, b: &EstrMap<EntityHashSet>) -> EstrMap<EntityHashSet> {
+    let mut result = EstrMap::default();
+    for (&name, targets) in a {
+        let missing: EntityHashSet = match b.get(&name) {
+            Some(other) => targets.difference(other).copied().collect(),
+            None => targets.clone(),
+        };
+        if !missing.is_empty() {
+            result.insert(name, missing);
+        }
+    }
+    result
+}
+
+// -----------------------------------------------------------------------------
+// Shared Links
+
+/// A read-only table of links shared between many entities.
+///
+/// When many entities have the same links, such as a group of turrets which
+/// all link to the same `"control_tower"`, storing a [`Links`] component on
+/// each of them duplicates the table. Instead, the table can be wrapped in a
+/// `SharedLinks`, which is cheap to clone onto every entity.
+///
+/// [`get_linked`][LinksExt::get_linked], [`list_linked`][LinksExt::list_linked]
+/// and the other [`LinksExt`] methods read through to the shared table.
+/// When an entity has both components, its own [`Links`] override the shared
+/// table per link name: any name the entity's [`Links`] has an entry for (even
+/// an empty one) is read from [`Links`], and every other name is read from
+/// the shared table. Changing a link with [`LinksCommandsExt`] first copies
+/// that link's targets from the shared table, so the change only affects the
+/// one entity. [`clear_all_links`][LinksCommandsExt::clear_all_links] removes
+/// the shared table.
+///
+/// Shared links are included in the [`LinkIndex`], so they keep
+/// [`RefCountedByLinks`] entities alive. World-level queries like
+/// [`incoming_links`][LinksWorldExt::incoming_links] only look at [`Links`]
+/// components, and links to despawned entities are only removed from
+/// [`Links`] components.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let tower = world.spawn_empty().id();
+/// let backup = world.spawn_empty().id();
+/// let mut table = Links::default();
+/// table.set("control_tower", tower);
+/// let table = SharedLinks::new(table);
+///
+/// let turrets: Vec<Entity> = (0..3).map(|_| world.spawn(table.clone()).id()).collect();
+/// for &turret in &turrets {
+///     assert_eq!(world.entity(turret).get_linked("control_tower"), Some(tower));
+/// }
+///
+/// // changes only affect one entity
+/// world.entity_mut(turrets[0]).set_link("control_tower", backup);
+/// world.entity_mut(turrets[1]).remove_link("control_tower", tower);
+/// assert_eq!(world.entity(turrets[0]).get_linked("control_tower"), Some(backup));
+/// assert_eq!(world.entity(turrets[1]).get_linked("control_tower"), None);
+/// assert_eq!(world.entity(turrets[2]).get_linked("control_tower"), Some(tower));
+/// ```
+#[derive(Component, Default, Clone)]
+#[component(on_insert = index_links)]
+#[component(on_replace = unindex_links)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Default, Clone)
+)]
+pub struct SharedLinks(Arc<Links>);
+
+impl SharedLinks {
+    pub fn new(links: Links) -> SharedLinks {
+        SharedLinks(Arc::new(links))
+    }
+}
+
+impl Deref for SharedLinks {
+    type Target = Links;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Links> for SharedLinks {
+    fn from(links: Links) -> Self {
+        SharedLinks::new(links)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Timed Links
+
+/// Stores links between entities, along with the time each link was added.
+///
+/// This works like [`Links`], but is a separate component, and is not used by
+/// the link extension traits or maintenance systems. Times are supplied by the
+/// caller, usually as the elapsed time from bevy's `Time` resource, so any
+/// clock can be used.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// # let [orc, troll, goblin] = [1, 2, 3].map(|i| Entity::from_raw_u32(i).unwrap());
+/// let mut links = TimedLinks::default();
+/// links.add("spotted", orc, Duration::from_secs(1));
+/// links.add("spotted", troll, Duration::from_secs(5));
+/// links.add("spotted", goblin, Duration::from_secs(3));
+///
+/// assert_eq!(links.most_recent("spotted"), Some(troll));
+/// assert_eq!(links.added_at("spotted", goblin), Some(Duration::from_secs(3)));
+///
+/// // adding a target again refreshes its time
+/// links.add("spotted", orc, Duration::from_secs(8));
+/// assert_eq!(links.most_recent("spotted"), Some(orc));
+/// ```
+#[derive(Component, Default, Clone)]
+#[component(map_entities)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Default, Clone, MapEntities)
+)]
+pub struct TimedLinks {
+    links: EstrMap<EntityHashMap<Duration>>,
+}
+
+impl TimedLinks {
+    /// Sets a link to a specific entity, added at the given time. The previous
+    /// value of this link will be overwritten.
+    pub fn set(&mut self, name: impl Into<Estr>, target: Entity, now: Duration) {
+        let link = self.links.entry(name.into()).or_default();
+        link.clear();
+        link.insert(target, now);
+    }
+
+    /// Adds a link to a specific entity, added at the given time. If the
+    /// entity is already linked, its time is updated.
+    pub fn add(&mut self, name: impl Into<Estr>, target: Entity, now: Duration) {
+        let link = self.links.entry(name.into()).or_default();
+        link.insert(target, now);
+    }
+
+    /// Removes an entity from a link.
+    pub fn remove(&mut self, name: impl Into<Estr>, target: Entity) {
+        if let Some(link) = self.links.get_mut(&name.into()) {
+            link.remove(&target);
+        }
+    }
+
+    /// Clears the value of a link.
+    pub fn clear(&mut self, name: impl Into<Estr>) {
+        self.links.remove(&name.into());
+    }
+
+    /// Returns true if the entity is linked under this name.
+    pub fn is_linked(&self, name: impl Into<Estr>, entity: Entity) -> bool {
+        self.added_at(name, entity).is_some()
+    }
+
+    /// Returns all linked entities.
+    pub fn list(&self, name: impl Into<Estr>) -> EntityHashSet {
+        self.links
+            .get(&name.into())
+            .map(|link| link.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the time at which an entity was linked under this name.
+    pub fn added_at(&self, name: impl Into<Estr>, target: Entity) -> Option<Duration> {
+        self.links.get(&name.into())?.get(&target).copied()
+    }
+
+    /// Returns the most recently linked entity. If several entities were
+    /// linked at the same time, any of them may be returned.
+    pub fn most_recent(&self, name: impl Into<Estr>) -> Option<Entity> {
+        self.links
+            .get(&name.into())?
+            .iter()
+            .max_by_key(|(_, added)| **added)
+            .map(|(&target, _)| target)
+    }
+}
+
+impl MapEntities for TimedLinks {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        for targets in self.links.values_mut() {
+            *targets = targets
+                .drain()
+                .map(|(target, added)| (entity_mapper.get_mapped(target), added))
+                .collect();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Link Index
+
+/// Indexes links by their targets, so the entities linking to a target can be
+/// found without visiting every [`Links`] component.
+///
+/// The index covers every link an entity can read, from its [`Links`] and its
+/// [`SharedLinks`]. It is updated by component hooks when either component is
+/// inserted, replaced, or removed, and by the [`LinksCommandsExt`] methods.
+/// Links changed directly through a `Mut<Links>` are indexed when
+/// [`index_changed_links`] next runs; the plugin runs it every frame in
+/// `PostUpdate`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let gandalf = world.spawn_empty().id();
+/// let bilbo = world.spawn_empty().set_link("talking_to", gandalf).id();
+/// let mut table = Links::default();
+/// table.set("talking_to", gandalf);
+/// let frodo = world.spawn(SharedLinks::new(table)).id();
+///
+/// let index = world.resource::<LinkIndex>();
+/// assert_eq!(index.count(gandalf, "talking_to"), 2);
+/// assert!(index.sources(gandalf, "talking_to").any(|source| source == frodo));
+///
+/// world.despawn(bilbo);
+/// let index = world.resource::<LinkIndex>();
+/// assert_eq!(index.sources(gandalf, "talking_to").collect::<Vec<_>>(), vec![frodo]);
+/// ```
+#[derive(Resource, Default)]
+pub struct LinkIndex {
+    /// The links each entity was last indexed with.
+    outgoing: EntityHashMap<EstrMap<EntityHashSet>>,
+    /// The entities linking to each target, by link name.
+    incoming: EntityHashMap<EstrMap<EntityHashSet>>,
+    /// The link name keeping each [`RefCountedByLinks`] entity alive.
+    counted: EntityHashMap<Estr>,
+    /// Ref-counted entities which have lost their last link, to be checked by
+    /// [`despawn_unreferenced`].
+    unreferenced: EntityHashSet,
+}
+
+impl LinkIndex {
+    /// Creates an iterator over the entities linking to `target` under the
+    /// given name, in no particular order.
+    pub fn sources(
+        &self,
+        target: Entity,
+        name: impl Into<Estr>,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        self.incoming
+            .get(&target)
+            .and_then(|names| names.get(&name.into()))
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// Returns the number of entities linking to `target` under the given
+    /// name.
+    pub fn count(&self, target: Entity, name: impl Into<Estr>) -> usize {
+        self.incoming
+            .get(&target)
+            .and_then(|names| names.get(&name.into()))
+            .map_or(0, EntityHashSet::len)
+    }
+
+    /// Replaces the indexed links of an entity. Returns the targets which were
+    /// not linked to before, to be marked with [`LinkTarget`].
+    fn index(&mut self, source: Entity, links: EstrMap<EntityHashSet>) -> Vec<Entity> {
+        let old = self.outgoing.remove(&source).unwrap_or_default();
+        for (&name, targets) in &old {
+            let kept = links.get(&name);
+            for &target in targets {
+                if !kept.is_some_and(|kept| kept.contains(&target)) {
+                    self.unlink(source, name, target);
+                }
+            }
+        }
+        let mut new_targets = Vec::new();
+        for (&name, targets) in &links {
+            for &target in targets {
+                self.incoming
+                    .entry(target)
+                    .or_insert_with(|| {
+                        new_targets.push(target);
+                        EstrMap::default()
+                    })
+                    .entry(name)
+                    .or_default()
+                    .insert(source);
+            }
+        }
+        if !links.is_empty() {
+            self.outgoing.insert(source, links);
+        }
+        new_targets
+    }
+
+    /// Removes one link from the index, noting ref-counted targets which have
+    /// lost their last link.
+    fn unlink(&mut self, source: Entity, name: Estr, target: Entity) {
+        let Some(names) = self.incoming.get_mut(&target) else {
+            return;
+        };
+        if let Some(sources) = names.get_mut(&name) {
+            sources.remove(&source);
+            if sources.is_empty() {
+                names.remove(&name);
+                if self.counted.get(&target) == Some(&name) {
+                    self.unreferenced.insert(target);
+                }
+            }
+        }
+        if names.is_empty() {
+            self.incoming.remove(&target);
+        }
+    }
+}
+
+/// Indexes the links of an entity when its [`Links`] or [`SharedLinks`] are
+/// inserted.
+fn index_links(mut world: DeferredWorld, context: HookContext) {
+    let source = context.entity;
+    let entity = world.entity(source);
+    let links = all_links(entity.get(), entity.get());
+    if let Some(mut index) = world.get_resource_mut::<LinkIndex>() {
+        let targets = index.index(source, links);
+        mark_link_targets(world.commands(), targets);
+    } else {
+        world.commands().queue(move |world: &mut World| {
+            let targets = world
+                .get_resource_or_init::<LinkIndex>()
+                .index(source, links);
+            mark_link_targets(world.commands(), targets);
+            world.flush();
+        });
+    }
+}
+
+/// Removes the links of an entity from the index when its [`Links`] or
+/// [`SharedLinks`] are replaced or removed. Whatever links the entity still
+/// has once the component is gone are indexed again afterwards.
+fn unindex_links(mut world: DeferredWorld, context: HookContext) {
+    let source = context.entity;
+    if let Some(mut index) = world.get_resource_mut::<LinkIndex>() {
+        index.index(source, EstrMap::default());
+    } else {
+        world.commands().queue(move |world: &mut World| {
+            let mut index = world.get_resource_or_init::<LinkIndex>();
+            index.index(source, EstrMap::default());
+        });
+    }
+    world
+        .commands()
+        .queue(move |world: &mut World| reindex_links(world, source));
+}
+
+/// Indexes the links an entity has now, if it still exists.
+fn reindex_links(world: &mut World, source: Entity) {
+    let Ok(entity) = world.get_entity(source) else {
+        return;
+    };
+    let links = all_links(entity.get(), entity.get());
+    let targets = world
+        .get_resource_or_init::<LinkIndex>()
+        .index(source, links);
+    mark_link_targets(world.commands(), targets);
+    world.flush();
+}
+
+/// Marks newly linked targets with [`LinkTarget`].
+fn mark_link_targets(mut commands: Commands, targets: Vec<Entity>) {
+    for target in targets {
+        commands.entity(target).try_insert(LinkTarget);
+    }
+}
+
+/// Matches entities whose [`Links`] or [`SharedLinks`] have changed.
+type LinksChanged = Or<(Changed<Links>, Changed<SharedLinks>)>;
+
+/// Updates the [`LinkIndex`] for links changed directly through their
+/// components, rather than with [`LinksCommandsExt`]. The plugin runs this
+/// system in `PostUpdate`, before [`despawn_unreferenced`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let gandalf = world.spawn_empty().id();
+/// let bilbo = world.spawn(Links::default()).id();
+///
+/// world.get_mut::<Links>(bilbo).unwrap().set("talking_to", gandalf);
+/// world.run_system_once(index_changed_links).unwrap();
+/// assert_eq!(world.resource::<LinkIndex>().count(gandalf, "talking_to"), 1);
+/// ```
+pub fn index_changed_links(
+    mut commands: Commands,
+    index: Option<ResMut<LinkIndex>>,
+    changed: Query<(Entity, Option<&Links>, Option<&SharedLinks>), LinksChanged>,
+) {
+    let Some(mut index) = index else {
+        return;
+    };
+    for (source, links, shared) in &changed {
+        let targets = index.index(source, all_links(links, shared));
+        mark_link_targets(commands.reborrow(), targets);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Reference Counting
+
+/// Keeps an entity alive only while it is the target of a link.
+///
+/// Entities with this component are despawned by [`despawn_unreferenced`]
+/// once the last link to them under the given link name is removed, counting
+/// links from both [`Links`] and [`SharedLinks`]. This is useful for shared
+/// resources, like a campfire that should burn only while someone is
+/// `"warming_at"` it. An entity which has never been linked is left alone, so
+/// it can be spawned before the entities that link to it.
+#[derive(Component, Copy, Clone, Debug)]
+#[component(on_insert = RefCountedByLinks::on_insert)]
+#[component(on_replace = RefCountedByLinks::on_replace)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Debug, Clone)
+)]
+pub struct RefCountedByLinks(Estr);
+
+impl RefCountedByLinks {
+    pub fn new(name: impl Into<Estr>) -> RefCountedByLinks {
+        RefCountedByLinks(name.into())
+    }
+
+    /// Returns the link name that keeps this entity alive.
+    pub fn name(&self) -> Estr {
+        self.0
+    }
+
+    fn on_insert(mut world: DeferredWorld, context: HookContext) {
+        let RefCountedByLinks(name) = *world
+            .entity(context.entity)
+            .get::<RefCountedByLinks>()
+            .unwrap();
+        if let Some(mut index) = world.get_resource_mut::<LinkIndex>() {
+            index.counted.insert(context.entity, name);
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut index = world.get_resource_or_init::<LinkIndex>();
+                index.counted.insert(context.entity, name);
+            });
+        }
+    }
+
+    fn on_replace(mut world: DeferredWorld, context: HookContext) {
+        if let Some(mut index) = world.get_resource_mut::<LinkIndex>() {
+            index.counted.remove(&context.entity);
+            index.unreferenced.remove(&context.entity);
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut index = world.get_resource_or_init::<LinkIndex>();
+                index.counted.remove(&context.entity);
+                index.unreferenced.remove(&context.entity);
+            });
+        }
+    }
+}
+
+/// Despawns every [`RefCountedByLinks`] entity whose last link with the
+/// configured name has been removed since this system last ran.
+///
+/// This only visits entities noted by the [`LinkIndex`] when their last link
+/// was removed, so its cost does not depend on the number of links in the
+/// world. An entity which was linked again before this system runs is kept.
+///
+/// Links held by a despawned entity disappear along with it, so when the last
+/// entity linking to a ref-counted entity is despawned, the ref-counted entity
+/// is despawned the next time this system runs.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let campfire = world.spawn(RefCountedByLinks::new("warming_at")).id();
+///
+/// // not yet linked, so not despawned
+/// world.run_system_once(despawn_unreferenced).unwrap();
+/// assert!(world.get_entity(campfire).is_ok());
+///
+/// let hobbit = world.spawn_empty().set_link("warming_at", campfire).id();
+/// let mut table = Links::default();
+/// table.set("warming_at", campfire);
+/// let dwarf = world.spawn(SharedLinks::new(table)).id();
+///
+/// world.entity_mut(hobbit).remove_link("warming_at", campfire);
+/// world.run_system_once(despawn_unreferenced).unwrap();
+/// assert!(world.get_entity(campfire).is_ok());
+///
+/// world.despawn(dwarf);
+/// world.run_system_once(despawn_unreferenced).unwrap();
+/// assert!(world.get_entity(campfire).is_err());
+/// ```
+pub fn despawn_unreferenced(mut commands: Commands, index: Option<ResMut<LinkIndex>>) {
+    let Some(mut index) = index else {
+        return;
+    };
+    let unreferenced = std::mem::take(&mut index.unreferenced);
+    for entity in unreferenced {
+        if let Some(&name) = index.counted.get(&entity)
+            && index.count(entity, name) == 0
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Symmetric Links
+
+/// Declares link names that should be kept in sync in both directions.
+///
+/// When a symmetric link from `a` to `b` is added or removed through
+/// [`LinksCommandsExt`], the reverse link from `b` back to `a` is added or
+/// removed as well. A link name may be its own reverse (like `"friends_with"`)
+/// or be paired with a different name (like `"parent_of"` and `"child_of"`).
+///
+/// Links edited directly through the [`Links`] component are not kept in
+/// sync. When an entity is despawned, [`remove_symmetric_links`] removes the
+/// back-links that pointed at it; this observer is added by the plugin.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut symmetric = SymmetricLinks::default();
+/// symmetric.declare("spouse", "spouse");
+///
+/// let mut world = World::new();
+/// world.insert_resource(symmetric);
+///
+/// let a = world.spawn_empty().id();
+/// let b = world.spawn_empty().id();
+///
+/// world.entity_mut(a).set_link("spouse", b);
+/// assert!(world.entity(b).is_linked("spouse", a));
+///
+/// world.entity_mut(a).remove_link("spouse", b);
+/// assert!(!world.entity(b).is_linked("spouse", a));
+/// ```
+///
+/// Setting a symmetric link also sets the back-link, so the target's previous
+/// partner is unlinked on both sides.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// # let mut symmetric = SymmetricLinks::default();
+/// # symmetric.declare("spouse", "spouse");
+/// # let mut world = World::new();
+/// # world.insert_resource(symmetric);
+/// let a = world.spawn_empty().id();
+/// let b = world.spawn_empty().id();
+/// let c = world.spawn_empty().id();
+///
+/// world.entity_mut(c).set_link("spouse", b);
+/// world.entity_mut(a).set_link("spouse", b);
+/// assert_eq!(world.entity(b).list_linked("spouse").into_iter().collect::<Vec<_>>(), vec![a]);
+/// assert!(!world.entity(c).is_linked("spouse", b));
+/// ```
+#[derive(Resource, Default)]
+pub struct SymmetricLinks {
+    reverse_names: EstrMap<Estr>,
+}
+
+impl SymmetricLinks {
+    /// Declares that `name` and `reverse` are reverses of each other. Use the
+    /// same name twice for a self-symmetric link.
+    pub fn declare(&mut self, name: impl Into<Estr>, reverse: impl Into<Estr>) {
+        let name = name.into();
+        let reverse = reverse.into();
+        self.reverse_names.insert(name, reverse);
+        self.reverse_names.insert(reverse, name);
+    }
+
+    /// Returns the reverse of a symmetric link name, if one has been declared.
+    pub fn reverse(&self, name: impl Into<Estr>) -> Option<Estr> {
+        self.reverse_names.get(&name.into()).copied()
+    }
+}
+
+/// An observer which removes symmetric back-links to an entity when it is
+/// despawned.
+pub fn remove_symmetric_links(
+    despawn: On<Despawn, Links>,
+    symmetric: Option<Res<SymmetricLinks>>,
+    mut links: Query<&mut Links>,
+) {
+    let Some(symmetric) = symmetric else {
+        return;
+    };
+    let source = despawn.entity;
+    let Ok(source_links) = links.get(source) else {
+        return;
+    };
+    let back_links: Vec<(Estr, Entity)> = source_links
+        .links
+        .iter()
+        .filter_map(|(name, targets)| Some((symmetric.reverse(*name)?, targets)))
+        .flat_map(|(reverse, targets)| targets.iter().map(move |&target| (reverse, target)))
+        .collect();
+    for (reverse, target) in back_links {
+        if let Ok(mut target_links) = links.get_mut(target) {
+            target_links.remove(reverse, source);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Dead Link Cleanup
+
+/// Marks an entity which is, or has been, the target of a link.
+///
+/// The [`LinkIndex`] adds this to each entity when it is first linked to, so
+/// that [`remove_links_to_despawned`] runs when it is despawned. There is no
+/// need to add it by hand.
+#[derive(Component, Default, Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, Clone)
+)]
+pub struct LinkTarget;
+
+/// An observer which removes the links to an entity when it is despawned.
+///
+/// Only the entities the [`LinkIndex`] lists as linking to the despawned
+/// entity are visited. Links read from [`SharedLinks`] are left in place,
+/// since the shared table can't be changed for one entity. This observer is
+/// added by the plugin.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.add_observer(remove_links_to_despawned);
+/// let gandalf = world.spawn_empty().id();
+/// let bilbo = world.spawn_empty().set_link("talking_to", gandalf).id();
+///
+/// world.despawn(gandalf);
+/// assert!(!world.entity(bilbo).has_link("talking_to"));
+/// assert_eq!(world.resource::<LinkIndex>().count(gandalf, "talking_to"), 0);
+/// ```
+pub fn remove_links_to_despawned(
+    despawn: On<Despawn, LinkTarget>,
+    index: Option<ResMut<LinkIndex>>,
+    mut links: Query<&mut Links>,
+) {
+    let Some(mut index) = index else {
+        return;
+    };
+    let target = despawn.entity;
+    let Some(names) = index.incoming.remove(&target) else {
+        return;
+    };
+    for (name, sources) in names {
+        for source in sources {
+            if let Some(outgoing) = index.outgoing.get_mut(&source) {
+                if let Some(targets) = outgoing.get_mut(&name) {
+                    targets.remove(&target);
+                    if targets.is_empty() {
+                        outgoing.remove(&name);
+                    }
+                }
+                if outgoing.is_empty() {
+                    index.outgoing.remove(&source);
+                }
+            }
+            if let Ok(mut links) = links.get_mut(source)
+                && let Some(targets) = links.links.get_mut(&name)
+            {
+                targets.remove(&target);
+                links.unweight(name, target);
+            }
+        }
+    }
+}
+
+/// Removes every link that points at an entity which no longer exists.
+///
+/// The plugin removes links as their targets are despawned, with
+/// [`remove_links_to_despawned`]. That relies on the [`LinkIndex`], which
+/// only sees links changed directly through a `Mut<Links>` when
+/// [`index_changed_links`] runs, so a target linked that way and despawned in
+/// the same frame leaves its links behind. Such links never resolve to
+/// another entity, since each [`Entity`] carries a generation, but they are
+/// still counted and listed. This system is the manual fallback.
+///
+/// This visits every link in the world, so it costs `O(n)` in the total number
+/// of links, and the plugin does not schedule it. It can be run as an
+/// exclusive system, or called directly.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let gandalf = world.spawn_empty().id();
+/// let bilbo = world.spawn_empty().set_link("talking_to", gandalf).id();
+///
+/// world.despawn(gandalf);
+/// prune_dead_links(&mut world);
+/// assert_eq!(world.entity(bilbo).get_linked("talking_to"), None);
+/// ```
+pub fn prune_dead_links(world: &mut World) {
+    let Some(mut query) = world.try_query::<(Entity, &Links)>() else {
+        return;
+    };
+    let dead: Vec<(Entity, Entity)> = query
+        .iter(world)
+        .flat_map(|(source, links)| {
+            links
+                .links
+                .values()
+                .flatten()
+                .map(move |&target| (source, target))
+        })
+        .filter(|&(_, target)| !world.entities().contains_spawned(target))
+        .collect();
+    for (source, target) in dead {
+        if let Some(mut links) = world.get_mut::<Links>(source) {
+            for targets in links.links.values_mut() {
+                targets.remove(&target);
+            }
+            for weights in links.weights.values_mut() {
+                weights.remove(&target);
+            }
+        }
+    }
 }
@@ -0,0 +1,48 @@
+//! Implements serde support for links, behind the `serialize` feature.
+//!
+//! Links are serialized as maps from link names to lists of target entities.
+//! Empty links are skipped.
+
+use std::fmt;
+
+use bevy_ecs::entity::{Entity, EntityHashSet};
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::Links;
+
+impl Serialize for Links {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.link_count()))?;
+        for (name, targets) in self.iter() {
+            let targets: Vec<Entity> = targets.iter().copied().collect();
+            map.serialize_entry(name.as_str(), &targets)?;
+        }
+        map.end()
+    }
+}
+
+struct LinksVisitor;
+
+impl<'de> Visitor<'de> for LinksVisitor {
+    type Value = Links;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of link names to lists of entities")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Links, A::Error> {
+        let mut links = Links::default();
+        while let Some((name, targets)) = access.next_entry::<String, Vec<Entity>>()? {
+            let targets: EntityHashSet = targets.into_iter().collect();
+            links.links.insert(name.into(), targets);
+        }
+        Ok(links)
+    }
+}
+
+impl<'de> Deserialize<'de> for Links {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(LinksVisitor)
+    }
+}
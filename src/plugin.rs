@@ -0,0 +1,279 @@
+//! Provides a plugin that sets up the crate for use in a bevy app.
+
+#[cfg(feature = "persist")]
+use std::path::PathBuf;
+
+#[cfg(feature = "persist")]
+use bevy_app::Last;
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::schedule::IntoScheduleConfigs;
+
+use crate::links::{
+    despawn_unreferenced, index_changed_links, remove_links_to_despawned, remove_symmetric_links,
+    LinkIndex, SymmetricLinks,
+};
+#[cfg(feature = "reflect")]
+use crate::links::{LinkTarget, Links, RefCountedByLinks, SharedLinks, TimedLinks};
+use crate::props::Props;
+#[cfg(feature = "persist")]
+use crate::props::{flush_global_props, load_global_props, save_global_props, PersistGlobalProps};
+#[cfg(feature = "reflect")]
+use crate::props::{PropEntity, Value};
+use crate::registry::{
+    name_by_class, send_change_messages, ClassDefaults, ClassHierarchy, Registry,
+};
+#[cfg(feature = "reflect")]
+use crate::registry::{Aliases, Class, Classes, Identity};
+
+/// Sets up props, links, and the registry.
+///
+/// This plugin:
+/// + Inserts an empty global [`Props`] resource (if there isn't one already).
+/// + Inserts an empty [`Registry`] resource (if there isn't one already).
+/// + Inserts empty [`ClassHierarchy`] and [`ClassDefaults`] resources (if
+///   they don't exist already).
+/// + Inserts a [`SymmetricLinks`] resource and adds the
+///   [`remove_symmetric_links`] observer.
+/// + Inserts an empty [`LinkIndex`] resource (if there isn't one already),
+///   and adds the [`remove_links_to_despawned`] observer.
+/// + Sends messages when names and classes change, if enabled with
+///   [`change_messages`][PropsPlugin::change_messages].
+/// + Names entities after their class, if enabled with
+///   [`auto_name_by_class`][PropsPlugin::auto_name_by_class].
+/// + Runs [`index_changed_links`] followed by [`despawn_unreferenced`] in
+///   [`PostUpdate`].
+/// + Registers the crate's types for reflection, when the `reflect` feature is
+///   enabled.
+///
+/// The plugin is optional. Without it, the resources are created lazily the
+/// first time they are needed, and maintenance systems must be scheduled by
+/// hand.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_mod_props::prelude::*;
+/// App::new().add_plugins(PropsPlugin::default());
+/// ```
+///
+/// # Reserved Names
+///
+/// Name prefixes can be reserved for internal entities with
+/// [`reserve_name_prefix`][PropsPlugin::reserve_name_prefix]. Names using a
+/// reserved prefix are rejected by
+/// [`set_name`][crate::registry::RegistryCommandsExt::set_name].
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::error::{ignore, DefaultErrorHandler};
+/// # use bevy_mod_props::prelude::*;
+/// let mut app = App::new();
+/// app.add_plugins(PropsPlugin::default().reserve_name_prefix("__"));
+/// # app.world_mut().insert_resource(DefaultErrorHandler(ignore));
+///
+/// app.world_mut().spawn_empty().set_name("__camera");
+/// assert!(app.world().lookup_name("__camera").is_err());
+/// ```
+///
+/// # Persistence
+///
+/// With the `persist` feature, the global [`Props`] resource can be mirrored
+/// to a file with [`persist_global_props`][PropsPlugin::persist_global_props].
+#[derive(Default)]
+pub struct PropsPlugin {
+    reserved_name_prefixes: Vec<String>,
+    symmetric_links: Vec<(String, String)>,
+    change_messages: bool,
+    auto_name_by_class: bool,
+    #[cfg(feature = "persist")]
+    persist_path: Option<PathBuf>,
+}
+
+impl PropsPlugin {
+    /// Reserves a name prefix. See
+    /// [`ReservedNamePrefixes`][crate::registry::ReservedNamePrefixes].
+    pub fn reserve_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.reserved_name_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Declares a symmetric link. See [`SymmetricLinks`].
+    pub fn symmetric_link(mut self, name: impl Into<String>, reverse: impl Into<String>) -> Self {
+        self.symmetric_links.push((name.into(), reverse.into()));
+        self
+    }
+
+    /// Enables the registry's messages:
+    /// [`NameAssigned`][crate::registry::NameAssigned],
+    /// [`NameChanged`][crate::registry::NameChanged],
+    /// [`NameRemoved`][crate::registry::NameRemoved], and
+    /// [`ClassChanged`][crate::registry::ClassChanged].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_plugins(PropsPlugin::default().change_messages());
+    ///
+    /// let ranger = app.world_mut().spawn_empty().set_name("strider").id();
+    /// app.world_mut().entity_mut(ranger).set_name("aragorn");
+    ///
+    /// let messages = app.world().resource::<Messages<NameChanged>>();
+    /// let renames: Vec<_> = messages.iter_current_update_messages().copied().collect();
+    /// assert_eq!(renames, vec![NameChanged {
+    ///     entity: ranger,
+    ///     old: "strider".into(),
+    ///     new: "aragorn".into(),
+    /// }]);
+    /// ```
+    pub fn change_messages(mut self) -> Self {
+        self.change_messages = true;
+        self
+    }
+
+    /// Names every entity which is given a [`Class`][crate::registry::Class]
+    /// but has no name after its class, as `wizard_0`, `wizard_1`, and so on.
+    /// This is useful while prototyping. See
+    /// [`name_by_class`][crate::registry::name_by_class].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_plugins(PropsPlugin::default().auto_name_by_class());
+    ///
+    /// let world = app.world_mut();
+    /// let first = world.spawn(Class::new("wizard")).id();
+    /// let second = world.spawn(Class::new("wizard")).id();
+    /// let gandalf = world.spawn_named_class("gandalf", "wizard").id();
+    ///
+    /// assert_eq!(world.lookup_name("wizard_0").unwrap(), first);
+    /// assert_eq!(world.lookup_name("wizard_1").unwrap(), second);
+    /// assert_eq!(world.entity(gandalf).get_name(), Some("gandalf".into()));
+    /// ```
+    pub fn auto_name_by_class(mut self) -> Self {
+        self.auto_name_by_class = true;
+        self
+    }
+
+    /// Loads the global [`Props`] resource from a RON file when the plugin is
+    /// built, and writes it back to the file whenever it changes. Writes are
+    /// debounced; see [`PersistGlobalProps`].
+    ///
+    /// If the file is missing or corrupt, the props start out empty (and a
+    /// warning is logged for corrupt files). Any existing global props are
+    /// replaced by the loaded ones.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy_app::App;
+    /// # use bevy_mod_props::prelude::*;
+    /// let path = std::env::temp_dir().join("bevy_mod_props_persist_doctest.ron");
+    /// # let _ = std::fs::remove_file(&path);
+    /// let mut app = App::new();
+    /// app.add_plugins(PropsPlugin::default().persist_global_props(&path));
+    /// app.world_mut().resource_mut::<PersistGlobalProps>().debounce = Duration::ZERO;
+    /// app.update();
+    ///
+    /// app.world_mut().set_prop("volume", 0.5);
+    /// app.update();
+    ///
+    /// let mut reloaded = App::new();
+    /// reloaded.add_plugins(PropsPlugin::default().persist_global_props(&path));
+    /// assert_eq!(reloaded.world().get_prop::<f32>("volume"), 0.5);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// Props changed before the first frame (for example in `Startup`) are
+    /// saved too, and pending writes are flushed when the app exits, even if
+    /// the debounce period has not passed.
+    ///
+    /// ```
+    /// # use bevy_app::{App, AppExit, Startup};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let path = std::env::temp_dir().join("bevy_mod_props_persist_exit_doctest.ron");
+    /// # let _ = std::fs::remove_file(&path);
+    /// let mut app = App::new();
+    /// app.add_plugins(PropsPlugin::default().persist_global_props(&path))
+    ///     .add_systems(Startup, |mut commands: Commands| {
+    ///         commands.set_prop("difficulty", "hard");
+    ///     });
+    /// app.update();
+    /// assert!(!path.exists());
+    ///
+    /// app.world_mut().write_message(AppExit::Success);
+    /// app.update();
+    /// let text = std::fs::read_to_string(&path).unwrap();
+    /// assert!(text.contains("hard"));
+    ///
+    /// let mut reloaded = App::new();
+    /// reloaded.add_plugins(PropsPlugin::default().persist_global_props(&path));
+    /// assert_eq!(reloaded.world().get_prop::<&str>("difficulty"), "hard");
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    #[cfg(feature = "persist")]
+    pub fn persist_global_props(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persist_path = Some(path.into());
+        self
+    }
+}
+
+impl Plugin for PropsPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "persist")]
+        if let Some(path) = &self.persist_path {
+            let props = load_global_props(path);
+            app.insert_resource(PersistGlobalProps::loaded(path.clone(), &props))
+                .insert_resource(props)
+                .add_systems(PostUpdate, save_global_props)
+                .add_systems(Last, flush_global_props);
+        }
+
+        app.init_resource::<Props>()
+            .init_resource::<Registry>()
+            .init_resource::<ClassHierarchy>()
+            .init_resource::<ClassDefaults>()
+            .init_resource::<SymmetricLinks>()
+            .init_resource::<LinkIndex>()
+            .add_observer(remove_symmetric_links)
+            .add_observer(remove_links_to_despawned)
+            .add_systems(
+                PostUpdate,
+                (index_changed_links, despawn_unreferenced).chain(),
+            );
+
+        if self.change_messages {
+            send_change_messages(app);
+        }
+
+        if self.auto_name_by_class {
+            app.add_observer(name_by_class);
+        }
+
+        let mut registry = app.world_mut().resource_mut::<Registry>();
+        for prefix in &self.reserved_name_prefixes {
+            registry.reserved_prefixes_mut().reserve(prefix.clone());
+        }
+
+        let mut symmetric = app.world_mut().resource_mut::<SymmetricLinks>();
+        for (name, reverse) in &self.symmetric_links {
+            symmetric.declare(name.as_str(), reverse.as_str());
+        }
+
+        #[cfg(feature = "reflect")]
+        app.register_type::<Value>()
+            .register_type::<Props>()
+            .register_type::<PropEntity>()
+            .register_type::<Links>()
+            .register_type::<LinkTarget>()
+            .register_type::<RefCountedByLinks>()
+            .register_type::<SharedLinks>()
+            .register_type::<TimedLinks>()
+            .register_type::<Identity>()
+            .register_type::<Class>()
+            .register_type::<Classes>()
+            .register_type::<Aliases>();
+    }
+}
@@ -0,0 +1,174 @@
+//! Contains a column-oriented store for hot properties
+
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    entity::{Entities, Entity},
+    resource::Resource,
+    system::ResMut,
+    world::World,
+};
+use estr::Estr;
+
+use super::{Props, Value, ValueType};
+
+// -----------------------------------------------------------------------------
+// Property Columns
+
+/// Stores a single property for many entities in one dense array.
+///
+/// Scanning a property stored in [`Props`] components means visiting a
+/// separate map on every entity. For hot properties read by many entities
+/// every frame (like the health of every enemy), a column keeps every value
+/// side-by-side in memory, indexed by entity.
+///
+/// Columns trade flexibility for speed: a column holds exactly one property
+/// name and one value type. Values in a column are not visible through
+/// [`Props`]. A column can be used as a resource, but there can only be one
+/// resource per value type; additional columns must be stored elsewhere.
+///
+/// Despawning an entity does not remove its value from a column. Call
+/// [`prune`][PropColumn::prune] to drop the values of despawned entities, or
+/// add the column with
+/// [`add_prop_column`][super::PropsAppExt::add_prop_column] to prune it
+/// every frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// for health in [10.0, 20.0, 30.0] {
+///     world.spawn_empty().set_prop("health", health);
+/// }
+///
+/// // scanning props components
+/// let mut query = world.query::<&Props>();
+/// let total: f32 = query.iter(&world).map(|props| props.get::<f32>("health")).sum();
+/// assert_eq!(total, 60.0);
+///
+/// // scanning a column
+/// let column = PropColumn::<f32>::promote(&mut world, "health");
+/// assert_eq!(column.values().sum::<f32>(), 60.0);
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct PropColumn<T: Send + Sync + 'static> {
+    name: Estr,
+    values: Vec<Option<(Entity, T)>>,
+}
+
+impl<T> PropColumn<T>
+where
+    T: ValueType + Copy + Send + Sync + 'static,
+    Option<T>: From<Value>,
+{
+    /// Creates a new empty column for the given property name.
+    pub fn new(name: impl Into<Estr>) -> PropColumn<T> {
+        PropColumn {
+            name: name.into(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Moves a property out of every [`Props`] component in the world and into
+    /// a new column. Properties of the wrong type are left in place.
+    pub fn promote(world: &mut World, name: impl Into<Estr>) -> PropColumn<T> {
+        let mut column = PropColumn::new(name);
+        let mut query = world.query::<(Entity, &mut Props)>();
+        for (entity, mut props) in query.iter_mut(world) {
+            if let Some(value) = Option::<T>::from(props[column.name]) {
+                props.remove(column.name);
+                column.set(entity, value);
+            }
+        }
+        column
+    }
+
+    /// Returns the name of the property stored in this column.
+    pub fn name(&self) -> Estr {
+        self.name
+    }
+
+    /// Returns the value for an entity, if it has one.
+    pub fn get(&self, entity: Entity) -> Option<T> {
+        match self.values.get(entity.index_u32() as usize) {
+            Some(Some((owner, value))) if *owner == entity => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Sets the value for an entity.
+    pub fn set(&mut self, entity: Entity, value: T) {
+        let index = entity.index_u32() as usize;
+        if index >= self.values.len() {
+            self.values.resize(index + 1, None);
+        }
+        self.values[index] = Some((entity, value));
+    }
+
+    /// Removes the value for an entity, returning it if there was one.
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let slot = self.values.get_mut(entity.index_u32() as usize)?;
+        match slot {
+            Some((owner, _)) if *owner == entity => slot.take().map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Removes the values of entities which are no longer spawned, returning
+    /// how many were removed. This visits every slot in the column.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let mut column = PropColumn::<f32>::new("health");
+    /// let entities: Vec<Entity> = (0..10_000).map(|_| world.spawn_empty().id()).collect();
+    /// for &entity in &entities {
+    ///     column.set(entity, 1.0);
+    /// }
+    /// for &entity in entities.iter().step_by(4) {
+    ///     world.despawn(entity);
+    /// }
+    ///
+    /// assert_eq!(column.prune(world.entities()), 2_500);
+    /// assert_eq!(column.values().sum::<f32>(), 7_500.0);
+    /// assert_eq!(column.get(entities[0]), None);
+    /// assert_eq!(column.get(entities[1]), Some(1.0));
+    /// ```
+    pub fn prune(&mut self, entities: &Entities) -> usize {
+        let mut removed = 0;
+        for slot in &mut self.values {
+            if let Some((owner, _)) = slot
+                && !entities.contains_spawned(*owner)
+            {
+                *slot = None;
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Creates a borrowing iterator over all entities and values in the column.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, T)> + '_ {
+        self.values.iter().flatten().copied()
+    }
+
+    /// Creates a borrowing iterator over all values in the column.
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+/// A system which removes the values of despawned entities from a
+/// [`PropColumn`] resource. See [`PropColumn::prune`].
+pub fn prune_prop_column<T>(column: Option<ResMut<PropColumn<T>>>, entities: &Entities)
+where
+    T: ValueType + Copy + Send + Sync + 'static,
+    Option<T>: From<Value>,
+{
+    if let Some(mut column) = column {
+        // Only trigger change detection when something was removed
+        if column.bypass_change_detection().prune(entities) > 0 {
+            column.set_changed();
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Contains a compact, indexed representation for enum-like string properties
+
+use bevy_ecs::resource::Resource;
+use estr::{Estr, EstrMap};
+use thiserror::Error;
+
+use super::{Props, Value};
+
+// -----------------------------------------------------------------------------
+// Errors
+
+#[derive(Debug, Error)]
+#[error("'{value}' is not a declared variant of enum property '{name}'")]
+pub struct UnknownEnumVariantError {
+    name: Estr,
+    value: Estr,
+}
+
+// -----------------------------------------------------------------------------
+// Enum Registry
+
+/// Declares the variants of enum-like string properties, so they can be stored
+/// as small numeric indices.
+///
+/// A property which is always one of a few strings (like a `"state"` which is
+/// `"idle"`, `"walking"` or `"running"`) can be stored as the index of its
+/// variant with [`Props::set_enum_indexed`], and read back as a string with
+/// [`Props::get_enum`]. Variants are indexed in the order they are declared,
+/// so new variants should only be added at the end.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// let mut enums = ValueEnumRegistry::default();
+/// enums.declare("state", ["idle", "walking", "running"]);
+///
+/// let mut props = Props::new();
+/// props.set_enum_indexed("state", "walking", &enums).unwrap();
+/// assert_eq!(props["state"], 1.0);
+/// assert_eq!(props.get_enum("state", &enums), Some(Estr::from("walking")));
+///
+/// assert!(props.set_enum_indexed("state", "flying", &enums).is_err());
+/// ```
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ValueEnumRegistry {
+    enums: EstrMap<Vec<Estr>>,
+}
+
+impl ValueEnumRegistry {
+    /// Declares the variants of an enum property, replacing any previous
+    /// declaration.
+    pub fn declare<S: Into<Estr>>(
+        &mut self,
+        name: impl Into<Estr>,
+        variants: impl IntoIterator<Item = S>,
+    ) {
+        let variants = variants.into_iter().map(Into::into).collect();
+        self.enums.insert(name.into(), variants);
+    }
+
+    /// Returns the index of a variant of an enum property.
+    pub fn index_of(&self, name: impl Into<Estr>, value: impl Into<Estr>) -> Option<usize> {
+        let value = value.into();
+        self.enums
+            .get(&name.into())?
+            .iter()
+            .position(|&variant| variant == value)
+    }
+
+    /// Returns the variant of an enum property at an index.
+    pub fn variant(&self, name: impl Into<Estr>, index: usize) -> Option<Estr> {
+        self.enums.get(&name.into())?.get(index).copied()
+    }
+}
+
+impl Props {
+    /// Sets an enum property, storing the index of the variant rather than
+    /// the string. Returns an error if the value is not a declared variant.
+    pub fn set_enum_indexed(
+        &mut self,
+        name: impl Into<Estr>,
+        value: impl Into<Estr>,
+        registry: &ValueEnumRegistry,
+    ) -> Result<(), UnknownEnumVariantError> {
+        let name = name.into();
+        let value = value.into();
+        let Some(index) = registry.index_of(name, value) else {
+            return Err(UnknownEnumVariantError { name, value });
+        };
+        self.set(name, index as f32);
+        Ok(())
+    }
+
+    /// Returns the variant of an enum property stored with
+    /// [`set_enum_indexed`][Props::set_enum_indexed], or `None` if the
+    /// property is not set or does not hold a valid index.
+    pub fn get_enum(&self, name: impl Into<Estr>, registry: &ValueEnumRegistry) -> Option<Estr> {
+        let name = name.into();
+        let Some(Value::Num(index)) = self.get_value(name) else {
+            return None;
+        };
+        if index < 0.0 || index.fract() != 0.0 {
+            return None;
+        }
+        registry.variant(name, index as usize)
+    }
+}
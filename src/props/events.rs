@@ -0,0 +1,55 @@
+//! Defines events triggered when properties change.
+
+use bevy_ecs::{entity::Entity, event::EntityEvent};
+use estr::Estr;
+
+use super::Value;
+
+/// Triggered when a property watched with
+/// [`watch_prop`][super::PropsAppExt::watch_prop] changes value. `old` is
+/// `None` if the property was not set before, and `new` is `None` if it was
+/// removed.
+#[derive(EntityEvent, Copy, Clone, PartialEq, Debug)]
+pub struct PropChanged {
+    pub entity: Entity,
+    pub name: Estr,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// The direction in which a property must cross a threshold to trigger a
+/// [`ThresholdCrossed`] event.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Edge {
+    /// The value went from below the threshold to at or above it.
+    Rising,
+    /// The value went from at or above the threshold to below it.
+    Falling,
+    /// The value crossed the threshold in either direction.
+    Both,
+}
+
+impl Edge {
+    /// Returns true if a change from `from` to `to` crosses the threshold in
+    /// this direction.
+    pub fn crosses(self, threshold: f32, from: f32, to: f32) -> bool {
+        let rising = from < threshold && to >= threshold;
+        let falling = from >= threshold && to < threshold;
+        match self {
+            Edge::Rising => rising,
+            Edge::Falling => falling,
+            Edge::Both => rising || falling,
+        }
+    }
+}
+
+/// Triggered when an entity's numeric property crosses a threshold watched
+/// with [`on_prop_threshold`][super::PropsAppExt::on_prop_threshold].
+#[derive(EntityEvent, Copy, Clone, PartialEq, Debug)]
+pub struct ThresholdCrossed {
+    pub entity: Entity,
+    pub name: Estr,
+    pub threshold: f32,
+    pub from: f32,
+    pub to: f32,
+}
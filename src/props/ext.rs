@@ -2,13 +2,21 @@
 
 use std::sync::LazyLock;
 
+use bevy_app::{App, PostUpdate};
 use bevy_ecs::{
-    system::{Commands, EntityCommands},
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    lifecycle::RemovedComponents,
+    query::{Changed, Has, With},
+    system::{Commands, EntityCommands, In, IntoSystem, Local, Query},
     world::{DeferredWorld, EntityRef, EntityWorldMut, World},
 };
 use estr::Estr;
 
-use super::{Props, Value};
+use super::{
+    prune_prop_column, Edge, PropChanged, PropColumn, PropSchema, Props, ThresholdCrossed, Value,
+    ValueType,
+};
 
 // -----------------------------------------------------------------------------
 // Immutable properties access
@@ -30,6 +38,15 @@ pub trait PropsExt {
     {
         self.props().get(name)
     }
+
+    /// Returns a property value, falling back to the default registered in
+    /// the schema and then to the default of the type. See [`PropSchema`].
+    fn get_prop_or_schema<T>(&self, name: impl Into<Estr>, schema: &PropSchema) -> T
+    where
+        T: From<Value> + Default + 'static,
+    {
+        self.props().get_or_schema(name, schema)
+    }
 }
 
 impl PropsExt for World {
@@ -79,12 +96,51 @@ pub trait PropsMutExt {
     /// Returns a mutable reference to a property value. If the propety value is
     /// of the wrong type or not set, a default value of the correct type will
     /// be inserted.
+    ///
+    /// On an entity, this inserts a [`Props`] component if there isn't one,
+    /// and marks the component as changed.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let legolas = world.spawn(Props::new().with("health", 100.0)).id();
+    /// world.clear_trackers();
+    /// assert!(!world.entity(legolas).get_ref::<Props>().unwrap().is_changed());
+    ///
+    /// *world.entity_mut(legolas).get_prop_mut::<f32>("health") -= 10.0;
+    /// assert_eq!(world.entity(legolas).get_prop::<f32>("health"), 90.0);
+    /// assert!(world.entity(legolas).get_ref::<Props>().unwrap().is_changed());
+    /// ```
     fn get_prop_mut<T>(&mut self, name: impl Into<Estr>) -> &mut T
     where
         Value: AsMut<T>,
     {
         self.props_mut().get_mut(name)
     }
+
+    /// Adds to a numeric property and returns the new value. See
+    /// [`Props::increment`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.increment_prop("orcs_slain", 1.0);
+    /// assert_eq!(world.increment_prop("orcs_slain", 1.0), 2.0);
+    ///
+    /// let mut gimli = world.spawn_empty();
+    /// assert_eq!(gimli.decrement_prop("axes", 1.0), -1.0);
+    /// ```
+    fn increment_prop(&mut self, name: impl Into<Estr>, amount: f32) -> f32 {
+        self.props_mut().increment(name, amount)
+    }
+
+    /// Subtracts from a numeric property and returns the new value. See
+    /// [`Props::decrement`].
+    fn decrement_prop(&mut self, name: impl Into<Estr>, amount: f32) -> f32 {
+        self.props_mut().decrement(name, amount)
+    }
 }
 
 impl PropsMutExt for World {
@@ -108,11 +164,45 @@ pub trait PropCommandsExt {
     /// Sets a property assoceated with this object.
     fn set_prop(&mut self, name: impl Into<Estr>, value: impl Into<Value>) -> &mut Self;
 
+    /// Flips a boolean property assoceated with this object. See
+    /// [`Props::toggle`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let mut bilbo = world.spawn_empty();
+    /// bilbo.toggle_prop("wearing_ring");
+    /// assert_eq!(bilbo.get_prop::<bool>("wearing_ring"), true);
+    /// ```
+    fn toggle_prop(&mut self, name: impl Into<Estr>) -> &mut Self;
+
     /// Removes a property from this object.
     fn remove_prop(&mut self, name: impl Into<Estr>) -> &mut Self;
 
     /// Clears all properties on this object.
     fn clear_props(&mut self) -> &mut Self;
+
+    /// Sets many properties at once, keeping any other properties already
+    /// set on this object.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let mut orc = world.spawn_empty();
+    /// orc.set_prop("hp", 10.0);
+    /// orc.set_props(props! { "name" => "grishnakh", "angry" => true });
+    /// orc.set_props([("hp", 30.0), ("armor", 5.0)]);
+    ///
+    /// assert_eq!(orc.get_prop::<f32>("hp"), 30.0);
+    /// assert_eq!(orc.get_prop::<f32>("armor"), 5.0);
+    /// assert_eq!(orc.get_prop::<Estr>("name"), "grishnakh");
+    /// ```
+    fn set_props<N: Into<Estr>, V: Into<Value>>(
+        &mut self,
+        props: impl IntoIterator<Item = (N, V)>,
+    ) -> &mut Self;
 }
 
 impl<P: PropsMutExt> PropCommandsExt for P {
@@ -121,6 +211,11 @@ impl<P: PropsMutExt> PropCommandsExt for P {
         self
     }
 
+    fn toggle_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
+        self.props_mut().toggle(name);
+        self
+    }
+
     fn remove_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
         self.props_mut().remove(name);
         self
@@ -130,6 +225,17 @@ impl<P: PropsMutExt> PropCommandsExt for P {
         self.props_mut().clear();
         self
     }
+
+    fn set_props<N: Into<Estr>, V: Into<Value>>(
+        &mut self,
+        props: impl IntoIterator<Item = (N, V)>,
+    ) -> &mut Self {
+        let props_mut = self.props_mut();
+        for (name, value) in props {
+            props_mut.set(name, value);
+        }
+        self
+    }
 }
 
 impl<'w, 's> PropCommandsExt for Commands<'w, 's> {
@@ -142,6 +248,14 @@ impl<'w, 's> PropCommandsExt for Commands<'w, 's> {
         self
     }
 
+    fn toggle_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
+        let name = name.into();
+        self.queue(move |world: &mut World| {
+            world.toggle_prop(name);
+        });
+        self
+    }
+
     fn remove_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
         let name = name.into();
         self.queue(move |world: &mut World| {
@@ -156,6 +270,20 @@ impl<'w, 's> PropCommandsExt for Commands<'w, 's> {
         });
         self
     }
+
+    fn set_props<N: Into<Estr>, V: Into<Value>>(
+        &mut self,
+        props: impl IntoIterator<Item = (N, V)>,
+    ) -> &mut Self {
+        let props: Vec<(Estr, Value)> = props
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        self.queue(move |world: &mut World| {
+            world.set_props(props);
+        });
+        self
+    }
 }
 
 impl<'a> PropCommandsExt for EntityCommands<'a> {
@@ -168,6 +296,14 @@ impl<'a> PropCommandsExt for EntityCommands<'a> {
         self
     }
 
+    fn toggle_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
+        let name = name.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.toggle_prop(name);
+        });
+        self
+    }
+
     fn remove_prop(&mut self, name: impl Into<Estr>) -> &mut Self {
         let name = name.into();
         self.queue(move |mut entity: EntityWorldMut| {
@@ -182,4 +318,376 @@ impl<'a> PropCommandsExt for EntityCommands<'a> {
         });
         self
     }
+
+    fn set_props<N: Into<Estr>, V: Into<Value>>(
+        &mut self,
+        props: impl IntoIterator<Item = (N, V)>,
+    ) -> &mut Self {
+        let props: Vec<(Estr, Value)> = props
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_props(props);
+        });
+        self
+    }
+}
+
+// -----------------------------------------------------------------------------
+// App extensions
+
+/// Adds property change callbacks to [`App`].
+pub trait PropsAppExt {
+    /// Runs a system for each entity whose [`Props`] changed since the last
+    /// frame. The entity is passed to the system as input.
+    ///
+    /// This runs in [`PostUpdate`] and relies on bevy's change detection, so
+    /// the system runs at most once per entity per frame no matter how many
+    /// properties were changed.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Rebuilds(usize);
+    ///
+    /// let mut app = App::new();
+    /// app.init_resource::<Rebuilds>()
+    ///     .on_any_prop_changed(|In(_): In<Entity>, mut rebuilds: ResMut<Rebuilds>| {
+    ///         rebuilds.0 += 1;
+    ///     });
+    ///
+    /// app.world_mut()
+    ///     .spawn_empty()
+    ///     .set_prop("health", 100.0)
+    ///     .set_prop("mana", 50.0)
+    ///     .set_prop("name", "gandalf");
+    /// app.update();
+    ///
+    /// assert_eq!(app.world().resource::<Rebuilds>().0, 1);
+    /// ```
+    fn on_any_prop_changed<M>(
+        &mut self,
+        system: impl IntoSystem<In<Entity>, (), M> + 'static,
+    ) -> &mut Self;
+
+    /// Adds a property computed from other properties on the same entity.
+    ///
+    /// The derived property is stored like any other, so reading it is cheap.
+    /// It is recomputed in [`PostUpdate`] whenever any of the `deps` on an
+    /// entity change (including being set or removed), and is only written
+    /// when the result differs from the stored value.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_derived_prop("power", &["strength", "level"], |props| {
+    ///     (props.get::<f32>("strength") * props.get::<f32>("level")).into()
+    /// });
+    ///
+    /// let hero = app.world_mut()
+    ///     .spawn_empty()
+    ///     .set_prop("strength", 3.0)
+    ///     .set_prop("level", 2.0)
+    ///     .id();
+    /// app.update();
+    /// assert_eq!(app.world().entity(hero).get_prop::<f32>("power"), 6.0);
+    ///
+    /// app.world_mut().entity_mut(hero).set_prop("level", 5.0);
+    /// app.update();
+    /// assert_eq!(app.world().entity(hero).get_prop::<f32>("power"), 15.0);
+    /// ```
+    fn add_derived_prop(
+        &mut self,
+        key: impl Into<Estr>,
+        deps: &[&str],
+        compute: fn(&Props) -> Value,
+    ) -> &mut Self;
+
+    /// Keeps a marker component in sync with a boolean property: entities
+    /// where the property is `true` have the marker, and all others do not.
+    /// This turns a property that is checked often into an archetype which
+    /// queries can filter on with `With` and `Without`.
+    ///
+    /// The marker is updated in [`PostUpdate`] when an entity's [`Props`]
+    /// change. Inserting or removing the marker by hand does not change the
+    /// property.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// #[derive(Component, Default)]
+    /// struct Aggro;
+    ///
+    /// let mut app = App::new();
+    /// app.add_prop_flag::<Aggro>("aggro");
+    ///
+    /// let orc = app.world_mut().spawn_empty().set_prop("aggro", true).id();
+    /// app.world_mut().spawn_empty().set_prop("aggro", false);
+    /// app.update();
+    ///
+    /// let mut aggro = app.world_mut().query_filtered::<Entity, With<Aggro>>();
+    /// assert_eq!(aggro.iter(app.world()).collect::<Vec<_>>(), vec![orc]);
+    ///
+    /// app.world_mut().entity_mut(orc).toggle_prop("aggro");
+    /// app.update();
+    /// assert_eq!(aggro.iter(app.world()).count(), 0);
+    /// ```
+    fn add_prop_flag<F: Component + Default>(&mut self, key: impl Into<Estr>) -> &mut Self;
+
+    /// Triggers a [`PropChanged`] event whenever a property on an entity
+    /// changes value, including when it is first set or is removed. Setting a
+    /// property to the value it already has does not count as a change.
+    ///
+    /// This runs in [`PostUpdate`], and compares the property with the value it
+    /// had the last time it was checked, so several changes within a frame
+    /// count as one. The event targets the entity, so an observer added with
+    /// [`EntityWorldMut::observe`] only hears about that entity's changes.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Flashes(Vec<(Option<Value>, Option<Value>)>);
+    ///
+    /// let mut app = App::new();
+    /// app.init_resource::<Flashes>().watch_prop("health");
+    ///
+    /// let hero = app.world_mut()
+    ///     .spawn(Props::new().with("health", 100.0))
+    ///     .observe(|changed: On<PropChanged>, mut flashes: ResMut<Flashes>| {
+    ///         flashes.0.push((changed.old, changed.new));
+    ///     })
+    ///     .id();
+    /// let villain = app.world_mut().spawn(Props::new().with("health", 100.0)).id();
+    /// app.update();
+    ///
+    /// app.world_mut().entity_mut(hero).set_prop("health", 90.0).set_prop("mana", 5.0);
+    /// app.world_mut().entity_mut(villain).set_prop("health", 50.0);
+    /// app.update();
+    ///
+    /// app.world_mut().entity_mut(hero).set_prop("health", 90.0);
+    /// app.update();
+    ///
+    /// assert_eq!(app.world().resource::<Flashes>().0, vec![
+    ///     (None, Some(Value::Num(100.0))),
+    ///     (Some(Value::Num(100.0)), Some(Value::Num(90.0))),
+    /// ]);
+    /// ```
+    fn watch_prop(&mut self, key: impl Into<Estr>) -> &mut Self;
+
+    /// Triggers a [`ThresholdCrossed`] event whenever a numeric property on an
+    /// entity crosses a threshold in the direction given by `edge`.
+    ///
+    /// This runs in [`PostUpdate`], and compares each property with the value
+    /// it had the last time it was checked, so several changes within a frame
+    /// count as one. The first value seen for an entity never counts as a
+    /// crossing, and neither does a property being removed or set to a
+    /// non-numeric value.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Warnings(Vec<(f32, f32)>);
+    ///
+    /// let mut app = App::new();
+    /// app.init_resource::<Warnings>()
+    ///     .on_prop_threshold("health", 20.0, Edge::Falling)
+    ///     .add_observer(|crossed: On<ThresholdCrossed>, mut warnings: ResMut<Warnings>| {
+    ///         warnings.0.push((crossed.from, crossed.to));
+    ///     });
+    ///
+    /// let hero = app.world_mut().spawn(Props::new().with("health", 100.0)).id();
+    /// for health in [50.0, 10.0, 5.0, 30.0, 15.0] {
+    ///     app.update();
+    ///     app.world_mut().entity_mut(hero).set_prop("health", health);
+    /// }
+    /// app.update();
+    ///
+    /// assert_eq!(app.world().resource::<Warnings>().0, vec![(50.0, 10.0), (30.0, 15.0)]);
+    /// ```
+    fn on_prop_threshold(&mut self, key: impl Into<Estr>, threshold: f32, edge: Edge) -> &mut Self;
+
+    /// Inserts a [`PropColumn`] resource, and prunes the values of despawned
+    /// entities from it in [`PostUpdate`] with [`prune_prop_column`].
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_prop_column(PropColumn::<f32>::new("health"));
+    ///
+    /// let orc = app.world_mut().spawn_empty().id();
+    /// let goblin = app.world_mut().spawn_empty().id();
+    /// let mut column = app.world_mut().resource_mut::<PropColumn<f32>>();
+    /// column.set(orc, 30.0);
+    /// column.set(goblin, 10.0);
+    ///
+    /// app.world_mut().despawn(goblin);
+    /// app.update();
+    /// assert_eq!(app.world().resource::<PropColumn<f32>>().values().sum::<f32>(), 30.0);
+    /// ```
+    fn add_prop_column<T>(&mut self, column: PropColumn<T>) -> &mut Self
+    where
+        T: ValueType + Copy + Send + Sync + 'static,
+        Option<T>: From<Value>;
+}
+
+impl PropsAppExt for App {
+    fn on_any_prop_changed<M>(
+        &mut self,
+        system: impl IntoSystem<In<Entity>, (), M> + 'static,
+    ) -> &mut Self {
+        let callback = self.world_mut().register_system(system);
+        self.add_systems(
+            PostUpdate,
+            move |mut commands: Commands, changed: Query<Entity, Changed<Props>>| {
+                for entity in &changed {
+                    commands.run_system_with(callback, entity);
+                }
+            },
+        )
+    }
+
+    fn add_derived_prop(
+        &mut self,
+        key: impl Into<Estr>,
+        deps: &[&str],
+        compute: fn(&Props) -> Value,
+    ) -> &mut Self {
+        let key = key.into();
+        let deps: Vec<Estr> = deps.iter().map(Estr::from).collect();
+        self.add_systems(
+            PostUpdate,
+            move |mut changed: Query<(Entity, &mut Props), Changed<Props>>,
+                  mut removed: RemovedComponents<Props>,
+                  mut last_deps: Local<EntityHashMap<Vec<Option<Value>>>>| {
+                for entity in removed.read() {
+                    last_deps.remove(&entity);
+                }
+                for (entity, mut props) in &mut changed {
+                    let dep_values: Vec<_> = deps
+                        .iter()
+                        .map(|dep| props.properties.get(dep).copied())
+                        .collect();
+                    if last_deps.get(&entity) == Some(&dep_values) {
+                        continue;
+                    }
+                    last_deps.insert(entity, dep_values);
+                    let derived = compute(&props);
+                    if props.properties.get(&key) != Some(&derived) {
+                        props.set(key, derived);
+                    }
+                }
+            },
+        )
+    }
+
+    fn add_prop_flag<F: Component + Default>(&mut self, key: impl Into<Estr>) -> &mut Self {
+        let key = key.into();
+        self.add_systems(
+            PostUpdate,
+            move |mut commands: Commands,
+                  changed: Query<(Entity, &Props, Has<F>), Changed<Props>>,
+                  mut removed: RemovedComponents<Props>,
+                  flagged: Query<(), With<F>>| {
+                for entity in removed.read() {
+                    if flagged.contains(entity) {
+                        commands.entity(entity).remove::<F>();
+                    }
+                }
+                for (entity, props, has_flag) in &changed {
+                    match (props.get::<bool>(key), has_flag) {
+                        (true, false) => {
+                            commands.entity(entity).insert(F::default());
+                        }
+                        (false, true) => {
+                            commands.entity(entity).remove::<F>();
+                        }
+                        _ => {}
+                    }
+                }
+            },
+        )
+    }
+
+    fn watch_prop(&mut self, key: impl Into<Estr>) -> &mut Self {
+        let key = key.into();
+        self.add_systems(
+            PostUpdate,
+            move |mut commands: Commands,
+                  changed: Query<(Entity, &Props), Changed<Props>>,
+                  mut removed: RemovedComponents<Props>,
+                  mut last: Local<EntityHashMap<Value>>| {
+                for entity in removed.read() {
+                    last.remove(&entity);
+                }
+                for (entity, props) in &changed {
+                    let new = props.properties.get(&key).copied();
+                    let old = match new {
+                        Some(value) => last.insert(entity, value),
+                        None => last.remove(&entity),
+                    };
+                    if old != new {
+                        commands.trigger(PropChanged {
+                            entity,
+                            name: key,
+                            old,
+                            new,
+                        });
+                    }
+                }
+            },
+        )
+    }
+
+    fn on_prop_threshold(&mut self, key: impl Into<Estr>, threshold: f32, edge: Edge) -> &mut Self {
+        let key = key.into();
+        self.add_systems(
+            PostUpdate,
+            move |mut commands: Commands,
+                  changed: Query<(Entity, &Props), Changed<Props>>,
+                  mut removed: RemovedComponents<Props>,
+                  mut last: Local<EntityHashMap<f32>>| {
+                for entity in removed.read() {
+                    last.remove(&entity);
+                }
+                for (entity, props) in &changed {
+                    let Some(&Value::Num(to)) = props.properties.get(&key) else {
+                        last.remove(&entity);
+                        continue;
+                    };
+                    if let Some(from) = last.insert(entity, to)
+                        && edge.crosses(threshold, from, to)
+                    {
+                        commands.trigger(ThresholdCrossed {
+                            entity,
+                            name: key,
+                            threshold,
+                            from,
+                            to,
+                        });
+                    }
+                }
+            },
+        )
+    }
+
+    fn add_prop_column<T>(&mut self, column: PropColumn<T>) -> &mut Self
+    where
+        T: ValueType + Copy + Send + Sync + 'static,
+        Option<T>: From<Value>,
+    {
+        self.insert_resource(column)
+            .add_systems(PostUpdate, prune_prop_column::<T>)
+    }
 }
@@ -0,0 +1,169 @@
+//! Contains a hash map backed alternative to `Props`
+
+use std::ops::Index;
+
+use bevy_ecs::{component::Component, resource::Resource};
+#[cfg(feature = "reflect")]
+use bevy_ecs::{reflect::ReflectComponent, reflect::ReflectResource};
+#[cfg(feature = "reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use estr::{Estr, EstrMap};
+
+use super::{Props, Value, ValueType, DEFAULT_VALUE};
+
+// -----------------------------------------------------------------------------
+// Hashed Property Maps
+
+/// A property store kept in a hash map rather than a sorted map.
+///
+/// [`Props`] keeps its properties sorted by name, so each lookup compares
+/// names `O(log n)` times. This map uses the precomputed hash of each
+/// interned name instead, so getting and setting a property takes the same
+/// time however many properties are set. This helps most on entities or
+/// resources with many properties; for a handful of properties the two are
+/// close.
+///
+/// Properties are not kept in order, so there is no prefix or range lookup.
+/// Use [`iter_sorted`][HashedProps::iter_sorted] when the order matters, and
+/// convert to and from [`Props`] to use the rest of the crate.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// let mut props = HashedProps::new();
+/// props.set("hp", 10.0);
+/// props.set("name", "bob");
+/// *props.get_mut::<f32>("hp") -= 3.0;
+/// assert_eq!(props.get::<f32>("hp"), 7.0);
+/// assert_eq!(props["name"], "bob");
+///
+/// let names: Vec<_> = props.iter_sorted().map(|(name, _)| name.as_str()).collect();
+/// assert_eq!(names, ["hp", "name"]);
+///
+/// let props = Props::from(props);
+/// assert_eq!(props["hp"], 7.0);
+/// ```
+#[derive(Component, Resource, Default, Clone, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Resource, Debug, Default, Clone)
+)]
+pub struct HashedProps {
+    properties: EstrMap<Value>,
+}
+
+impl HashedProps {
+    /// Creates a new empty set of properties.
+    pub fn new() -> HashedProps {
+        HashedProps::default()
+    }
+
+    /// Returns a property value. If the property is of the wrong type or is
+    /// not set, a default value is returned instead.
+    pub fn get<T>(&self, name: impl Into<Estr>) -> T
+    where
+        T: From<Value> + Default + 'static,
+    {
+        if let Some(&value) = self.properties.get(&name.into()) {
+            value.into()
+        } else {
+            T::default()
+        }
+    }
+
+    /// Returns a mutable reference to a property value. If the propety value
+    /// is of the wrong type or not set, a default value of the correct type
+    /// will be inserted.
+    pub fn get_mut<T>(&mut self, name: impl Into<Estr>) -> &mut T
+    where
+        Value: AsMut<T>,
+    {
+        self.properties.entry(name.into()).or_default().as_mut()
+    }
+
+    /// Returns a property value, first setting it to a default if it is not
+    /// set or is of the wrong type.
+    pub fn get_or_insert<T>(&mut self, name: impl Into<Estr>, default: T) -> T
+    where
+        T: ValueType + From<Value>,
+    {
+        let default = default.into();
+        let value = self.properties.entry(name.into()).or_insert(default);
+        if value.kind() != T::KIND {
+            *value = default;
+        }
+        (*value).into()
+    }
+
+    /// Sets a property value.
+    pub fn set(&mut self, name: impl Into<Estr>, value: impl Into<Value>) {
+        self.properties.insert(name.into(), value.into());
+    }
+
+    /// Sets a property value, and returns this object.
+    pub fn with(mut self, name: impl Into<Estr>, value: impl Into<Value>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Removes a property.
+    pub fn remove(&mut self, name: impl Into<Estr>) {
+        self.properties.remove(&name.into());
+    }
+
+    /// Clears all properties.
+    pub fn clear(&mut self) {
+        self.properties.clear();
+    }
+
+    /// Returns the number of properties which are set.
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Returns true if no properties are set.
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Creates a borrowing iterator over all property names and values, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Estr, &Value)> {
+        self.properties.iter()
+    }
+
+    /// Creates a borrowing iterator over all property names and values,
+    /// sorted by name as in [`Props`]. This sorts the properties each time it
+    /// is called.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&Estr, &Value)> {
+        let mut properties: Vec<_> = self.properties.iter().collect();
+        properties.sort_unstable_by_key(|&(name, _)| name);
+        properties.into_iter()
+    }
+}
+
+impl<S: Into<Estr>> Index<S> for HashedProps {
+    type Output = Value;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.properties.get(&index.into()).unwrap_or(&DEFAULT_VALUE)
+    }
+}
+
+impl From<Props> for HashedProps {
+    fn from(props: Props) -> HashedProps {
+        HashedProps {
+            properties: props.into_iter().collect(),
+        }
+    }
+}
+
+impl From<HashedProps> for Props {
+    fn from(props: HashedProps) -> Props {
+        let mut result = Props::new();
+        for (name, value) in props.properties {
+            result.set(name, value);
+        }
+        result
+    }
+}
@@ -78,16 +78,47 @@
 //!
 
 use std::collections::btree_map::*;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fmt;
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 use bevy_ecs::component::Component;
+use bevy_ecs::name::Name;
 use bevy_ecs::resource::Resource;
+#[cfg(feature = "reflect")]
+use bevy_ecs::{reflect::ReflectComponent, reflect::ReflectResource};
+#[cfg(feature = "reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "reflect", feature = "serialize"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use estr::Estr;
+use thiserror::Error;
 
+mod column;
+mod enums;
+mod events;
 mod ext;
+mod hashed;
+#[cfg(feature = "persist")]
+mod persist;
+mod schema;
+#[cfg(feature = "serialize")]
+mod serialize;
+mod snapshot;
+#[cfg(feature = "derive")]
+pub use bevy_mod_props_derive::IntoProps;
+pub use column::*;
+pub use enums::*;
+pub use events::*;
 pub use ext::*;
+pub use hashed::*;
+#[cfg(feature = "persist")]
+pub use persist::*;
+pub use schema::*;
+pub use snapshot::*;
 
 // -----------------------------------------------------------------------------
 // The Value Type
@@ -95,9 +126,19 @@ pub use ext::*;
 /// A boolean, number or string.
 ///
 /// Values may be either a boolean, number, or string. You can use `Into/From` to
-/// convert from normal rust datatypes into values, and `TryInto/TryFrom` to
-/// convert back. Using `TryFrom` will return an error if the types do not
-/// match.
+/// convert from normal rust datatypes into values, and back again. Converting
+/// back with `From` is lossy: if the types do not match, the default value of
+/// the requested type is returned. Use [`try_into_typed`][Value::try_into_typed]
+/// to get a [`ValueTypeError`] instead. (The standard `TryFrom` trait can't be
+/// used for this, because the lossy `From` conversions already implement it.)
+///
+/// ```rust
+/// # use bevy_mod_props::prelude::*;
+/// let value = Value::from("hello");
+/// assert_eq!(f32::from(value), 0.0);
+/// assert!(value.try_into_typed::<f32>().is_err());
+/// assert_eq!(value.try_into_typed::<Estr>().unwrap(), "hello");
+/// ```
 ///
 /// # Value Access
 ///
@@ -126,6 +167,13 @@ pub use ext::*;
 /// Two values are equal if they contain equal values of the same type. Values
 /// with different types are never equal. `Value::num(NaN)` is equal to nothing.
 ///
+/// # Non-Finite Numbers
+///
+/// Numbers are stored exactly as given, so a stray division by zero can store
+/// `NaN` or an infinity. Use [`is_finite`][Value::is_finite] to guard against
+/// this, or [`Props::set_checked`] to reject `NaN` and clamp infinities when
+/// setting a property.
+///
 /// # Math
 ///
 /// `Value` supports the basic algebraic operations: [`Add`], [`Sub`], [`Mul`],
@@ -136,6 +184,15 @@ pub use ext::*;
 ///
 /// Doing any kind of math with `Value` always returns a `Value::Num` variant.
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Debug, Default, Clone)
+)]
+#[cfg_attr(
+    all(feature = "reflect", feature = "serialize"),
+    reflect(Serialize, Deserialize)
+)]
 pub enum Value {
     Bool(bool),
     Num(f32),
@@ -151,15 +208,186 @@ impl Default for Value {
     }
 }
 
+impl Value {
+    /// Returns true if this value is the default for its own kind: `false`,
+    /// `0.0`, or the empty string.
+    ///
+    /// This differs from comparing with [`Value::default()`], which is always
+    /// `false`.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert!(Value::Num(0.0).is_default());
+    /// assert!(Value::from("").is_default());
+    /// assert!(!Value::Num(1.0).is_default());
+    /// ```
+    pub fn is_default(&self) -> bool {
+        match self {
+            Value::Bool(value) => !value,
+            Value::Num(value) => *value == 0.0,
+            Value::Str(value) => value.is_empty(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Kinds
+
+/// The kind of data held by a [`Value`], without the data itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ValueKind {
+    Bool,
+    Num,
+    Str,
+}
+
+impl Value {
+    /// Returns the kind of data held by this value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Num(_) => ValueKind::Num,
+            Value::Str(_) => ValueKind::Str,
+        }
+    }
+}
+
+impl Props {
+    /// Returns the kind of every property, which describes the shape of the
+    /// properties without their values. Comparing schemas is a cheap way to
+    /// check whether saved properties need migrating.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let props = Props::new().with("alive", true).with("hp", 10.0);
+    /// let schema = props.schema_of();
+    ///
+    /// assert_eq!(schema[&Estr::from("alive")], ValueKind::Bool);
+    /// assert_eq!(schema[&Estr::from("hp")], ValueKind::Num);
+    /// ```
+    pub fn schema_of(&self) -> BTreeMap<Estr, ValueKind> {
+        self.properties
+            .iter()
+            .map(|(&name, value)| (name, value.kind()))
+            .collect()
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueKind::Bool => write!(f, "bool"),
+            ValueKind::Num => write!(f, "num"),
+            ValueKind::Str => write!(f, "str"),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Printing
 
+/// Values are printed like the type they hold, and respect the formatter's
+/// options, such as width and precision.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// assert_eq!(format!("{}", Value::Num(100.0)), "100");
+/// assert_eq!(format!("{:.2}", Value::Num(100.0)), "100.00");
+/// assert_eq!(format!("{:>5}", Value::Str("hp".into())), "   hp");
+/// assert_eq!(format!("{:<6}|", Value::Bool(true)), "true  |");
+/// ```
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Value::Bool(bool) => write!(f, "{bool}"),
-            Value::Num(num) => write!(f, "{num}"),
-            Value::Str(estr) => write!(f, "{estr}"),
+            Value::Bool(bool) => fmt::Display::fmt(bool, f),
+            Value::Num(num) => fmt::Display::fmt(num, f),
+            Value::Str(estr) => fmt::Display::fmt(estr.as_str(), f),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Parsing
+
+/// Values are parsed by inferring their type: `"true"` and `"false"` become
+/// booleans, finite numbers become numbers, and anything else becomes a
+/// string. Parsing never fails.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// assert_eq!("true".parse::<Value>(), Ok(Value::Bool(true)));
+/// assert_eq!("42.5".parse::<Value>(), Ok(Value::Num(42.5)));
+/// assert_eq!("-3".parse::<Value>(), Ok(Value::Num(-3.0)));
+/// assert_eq!("gandalf".parse::<Value>(), Ok(Value::Str("gandalf".into())));
+/// assert_eq!("nan".parse::<Value>(), Ok(Value::Str("nan".into())));
+/// ```
+impl FromStr for Value {
+    type Err = Infallible;
+
+    fn from_str(str: &str) -> Result<Value, Infallible> {
+        if let Ok(bool) = str.parse::<bool>() {
+            return Ok(Value::Bool(bool));
+        }
+        if let Ok(num) = str.parse::<f32>()
+            && num.is_finite()
+        {
+            return Ok(Value::Num(num));
+        }
+        Ok(Value::Str(Estr::from(str)))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Visitors
+
+/// Handles each kind of [`Value`], for use with [`Value::accept`].
+///
+/// Implementing this trait rather than matching on [`Value`] means that adding
+/// a new kind of value is a compile error in every visitor, rather than a
+/// silent fallthrough to a wildcard arm.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// struct Stringify;
+///
+/// impl ValueVisitor for Stringify {
+///     type Output = String;
+///
+///     fn visit_bool(&mut self, value: bool) -> String {
+///         format!("bool:{value}")
+///     }
+///
+///     fn visit_num(&mut self, value: f32) -> String {
+///         format!("num:{value}")
+///     }
+///
+///     fn visit_str(&mut self, value: Estr) -> String {
+///         format!("str:{value}")
+///     }
+/// }
+///
+/// assert_eq!(Value::Bool(true).accept(&mut Stringify), "bool:true");
+/// assert_eq!(Value::Num(4.5).accept(&mut Stringify), "num:4.5");
+/// assert_eq!(Value::Str("ring".into()).accept(&mut Stringify), "str:ring");
+/// ```
+pub trait ValueVisitor {
+    /// The result of visiting a value.
+    type Output;
+
+    fn visit_bool(&mut self, value: bool) -> Self::Output;
+
+    fn visit_num(&mut self, value: f32) -> Self::Output;
+
+    fn visit_str(&mut self, value: Estr) -> Self::Output;
+}
+
+impl Value {
+    /// Calls the method of the visitor which matches this value's kind.
+    pub fn accept<V: ValueVisitor>(&self, visitor: &mut V) -> V::Output {
+        match *self {
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::Num(value) => visitor.visit_num(value),
+            Value::Str(value) => visitor.visit_str(value),
         }
     }
 }
@@ -188,6 +416,50 @@ impl From<Value> for bool {
     }
 }
 
+impl Value {
+    /// Returns the boolean held by this value, or `None` if it is not a
+    /// boolean. Converting to `bool` is just as strict, but reads `false` for
+    /// any other kind of value.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert_eq!(Value::Bool(true).to_bool_strict(), Some(true));
+    /// assert_eq!(Value::Num(1.0).to_bool_strict(), None);
+    /// assert_eq!(Value::from("yes").to_bool_strict(), None);
+    ///
+    /// assert_eq!(bool::from(Value::Bool(true)), true);
+    /// assert_eq!(bool::from(Value::Num(1.0)), false);
+    /// assert_eq!(bool::from(Value::from("yes")), false);
+    /// ```
+    pub fn to_bool_strict(&self) -> Option<bool> {
+        match self {
+            Value::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this value is "truthy": `true`, a number other than
+    /// zero or NaN, or a non-empty string.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert!(Value::Bool(true).is_truthy());
+    /// assert!(Value::Num(1.0).is_truthy());
+    /// assert!(Value::from("yes").is_truthy());
+    ///
+    /// assert!(!Value::Bool(false).is_truthy());
+    /// assert!(!Value::Num(0.0).is_truthy());
+    /// assert!(!Value::from("").is_truthy());
+    /// ```
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(bool) => *bool,
+            Value::Num(num) => *num != 0.0 && !num.is_nan(),
+            Value::Str(str) => !str.as_str().is_empty(),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Numeric Type Conversions
 
@@ -233,6 +505,53 @@ impl From<Value> for f64 {
     }
 }
 
+impl Value {
+    /// Returns the number held by this value, or `None` if it is not a
+    /// number. Unlike converting to `f32`, this distinguishes a real zero from
+    /// a value of the wrong kind.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert_eq!(Value::Num(0.0).num_or_none(), Some(0.0));
+    /// assert_eq!(Value::from("zero").num_or_none(), None);
+    /// ```
+    pub fn num_or_none(&self) -> Option<f32> {
+        match self {
+            Value::Num(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    /// Returns the number held by this value, or `fallback` if it is not a
+    /// number.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert_eq!(Value::Num(2.5).num_or(1.0), 2.5);
+    /// assert_eq!(Value::Bool(true).num_or(1.0), 1.0);
+    /// ```
+    pub fn num_or(&self, fallback: f32) -> f32 {
+        self.num_or_none().unwrap_or(fallback)
+    }
+
+    /// Returns false if this value is `NaN` or an infinity, and true for every
+    /// other value, including values which are not numbers.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// assert!(Value::Num(1.0).is_finite());
+    /// assert!(Value::Bool(true).is_finite());
+    /// assert!(!Value::Num(f32::NAN).is_finite());
+    /// assert!(!Value::Num(1.0 / 0.0).is_finite());
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        match self {
+            Value::Num(num) => num.is_finite(),
+            _ => true,
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // String Type Conversions
 
@@ -299,6 +618,93 @@ impl From<Value> for Estr {
     }
 }
 
+impl Value {
+    /// Converts a string value into a bevy [`Name`]. Values that are not
+    /// strings produce an empty name.
+    ///
+    /// ```rust
+    /// # use bevy_ecs::name::Name;
+    /// # use bevy_mod_props::prelude::*;
+    /// let display_name = Value::from("Gandalf the Grey");
+    /// assert_eq!(display_name.into_name(), Name::new("Gandalf the Grey"));
+    /// ```
+    pub fn into_name(self) -> Name {
+        let name: &'static str = self.into();
+        Name::new(name)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Fallible Conversions
+
+#[derive(Debug, Error)]
+#[error("expected a {expected} value, found a {found} value")]
+pub struct ValueTypeError {
+    pub expected: ValueKind,
+    pub found: ValueKind,
+}
+
+#[derive(Debug, Error)]
+#[error("cannot set property '{name}' to NaN")]
+pub struct NanValueError {
+    pub name: Estr,
+}
+
+/// A rust type that can be stored in a [`Value`].
+pub trait ValueType: Into<Value> {
+    /// The kind of value this type is stored as.
+    const KIND: ValueKind;
+}
+
+impl ValueType for bool {
+    const KIND: ValueKind = ValueKind::Bool;
+}
+
+impl ValueType for f32 {
+    const KIND: ValueKind = ValueKind::Num;
+}
+
+impl ValueType for f64 {
+    const KIND: ValueKind = ValueKind::Num;
+}
+
+impl ValueType for &str {
+    const KIND: ValueKind = ValueKind::Str;
+}
+
+impl ValueType for String {
+    const KIND: ValueKind = ValueKind::Str;
+}
+
+impl ValueType for Estr {
+    const KIND: ValueKind = ValueKind::Str;
+}
+
+impl Value {
+    /// Converts this value into the requested type, returning an error rather
+    /// than a default if the types do not match.
+    ///
+    /// ```rust
+    /// # use bevy_mod_props::prelude::*;
+    /// let value = Value::from(42.0);
+    /// assert_eq!(value.try_into_typed::<f32>().unwrap(), 42.0);
+    ///
+    /// let error = value.try_into_typed::<bool>().unwrap_err();
+    /// assert_eq!(error.expected, ValueKind::Bool);
+    /// assert_eq!(error.found, ValueKind::Num);
+    /// ```
+    pub fn try_into_typed<T>(self) -> Result<T, ValueTypeError>
+    where
+        T: ValueType,
+        Option<T>: From<Value>,
+    {
+        Option::<T>::from(self).ok_or(ValueTypeError {
+            expected: T::KIND,
+            found: self.kind(),
+        })
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Referencing and casting
 
@@ -562,6 +968,76 @@ impl PartialOrd<Value> for Value {
     }
 }
 
+impl Value {
+    /// Returns an ordering between any two values. Values of different kinds
+    /// are ordered by kind (booleans, then numbers, then strings), and values
+    /// of the same kind are ordered normally. Numbers are compared with
+    /// [`f32::total_cmp`].
+    pub fn total_cmp(&self, other: &Value) -> std::cmp::Ordering {
+        match (self, other) {
+            (Value::Bool(this), Value::Bool(that)) => this.cmp(that),
+            (Value::Num(this), Value::Num(that)) => this.total_cmp(that),
+            (Value::Str(this), Value::Str(that)) => this.as_str().cmp(that.as_str()),
+            _ => self.kind().cmp(&other.kind()),
+        }
+    }
+
+    /// Compares two string values as dot-separated version numbers, so that
+    /// `"1.10"` is greater than `"1.9"`. Segments which are not numbers are
+    /// compared as strings, and a version with extra segments is greater than
+    /// its prefix. Values which are not both strings are compared with
+    /// [`total_cmp`][Value::total_cmp].
+    ///
+    /// ```
+    /// # use std::cmp::Ordering;
+    /// # use bevy_mod_props::prelude::*;
+    /// let old = Value::from("1.9");
+    /// let new = Value::from("1.10");
+    /// assert_eq!(new.version_cmp(&old), Ordering::Greater);
+    /// assert_eq!(Value::from("2.0-beta").version_cmp(&"2.0-alpha".into()), Ordering::Greater);
+    /// ```
+    pub fn version_cmp(&self, other: &Value) -> std::cmp::Ordering {
+        let (Value::Str(this), Value::Str(that)) = (self, other) else {
+            return self.total_cmp(other);
+        };
+        let mut these = this.as_str().split('.');
+        let mut those = that.as_str().split('.');
+        loop {
+            let ordering = match (these.next(), those.next()) {
+                (None, None) => return std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (Some(this), Some(that)) => match (this.parse::<u64>(), that.parse::<u64>()) {
+                    (Ok(this), Ok(that)) => this.cmp(&that),
+                    _ => this.cmp(that),
+                },
+            };
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+    }
+
+    /// Returns the greatest value, according to [`total_cmp`][Value::total_cmp],
+    /// or `None` if there are no values.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let values = [Value::Num(3.0), Value::Bool(true), Value::Num(7.0)];
+    /// assert_eq!(Value::max_of(values), Some(Value::Num(7.0)));
+    /// assert_eq!(Value::min_of(values), Some(Value::Bool(true)));
+    /// ```
+    pub fn max_of(values: impl IntoIterator<Item = Value>) -> Option<Value> {
+        values.into_iter().max_by(Value::total_cmp)
+    }
+
+    /// Returns the least value, according to [`total_cmp`][Value::total_cmp],
+    /// or `None` if there are no values.
+    pub fn min_of(values: impl IntoIterator<Item = Value>) -> Option<Value> {
+        values.into_iter().min_by(Value::total_cmp)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Addition
 
@@ -773,6 +1249,55 @@ impl DivAssign<Value> for Value {
     }
 }
 
+// -----------------------------------------------------------------------------
+// Rounding
+
+// Like the arithmetic operators, rounding treats values that do not contain
+// numbers as if they contained zero.
+
+impl Value {
+    /// Applies a function to the number held by this value, treating values
+    /// which are not numbers as zero.
+    fn map_num(self, f: impl FnOnce(f32) -> f32) -> Value {
+        match self {
+            Value::Num(num) => Value::Num(f(num)),
+            _ => Value::Num(f(0.0)),
+        }
+    }
+
+    /// Rounds a number down. See [`f32::floor`].
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let props = Props::new().with("x", 2.7).with("y", -2.7);
+    /// assert_eq!(props["x"].floor(), 2.0);
+    /// assert_eq!(props["y"].floor(), -3.0);
+    /// assert_eq!(props["x"].ceil(), 3.0);
+    /// assert_eq!(props["y"].round(), -3.0);
+    /// assert_eq!(props["y"].trunc(), -2.0);
+    /// assert_eq!(Value::from("tile").floor(), 0.0);
+    /// ```
+    pub fn floor(self) -> Value {
+        self.map_num(f32::floor)
+    }
+
+    /// Rounds a number up. See [`f32::ceil`].
+    pub fn ceil(self) -> Value {
+        self.map_num(f32::ceil)
+    }
+
+    /// Rounds a number to the nearest integer, rounding half-way cases away
+    /// from zero. See [`f32::round`].
+    pub fn round(self) -> Value {
+        self.map_num(f32::round)
+    }
+
+    /// Rounds a number towards zero. See [`f32::trunc`].
+    pub fn trunc(self) -> Value {
+        self.map_num(f32::trunc)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Property Maps
 
@@ -786,9 +1311,33 @@ impl DivAssign<Value> for Value {
 /// When accessing a property, if a value has not been set or has the wrong
 /// type, the property should be treated as if it has the default value of the
 /// correct type. For example, toggling a
+///
+/// # Performance
+///
+/// Properties are kept in a map sorted by name. This is what makes
+/// [`iter_prefix`][Props::iter_prefix] and [`range`][Props::range] cheap, and
+/// keeps iteration and serialized output in a stable order. The cost is that
+/// lookups take `O(log n)` string comparisons, rather than the single hash of
+/// an interned name that a hash map would need. For the handful of
+/// properties most entities have, the difference is small. It grows with
+/// the number of properties on one map. For maps with many properties that
+/// are rarely iterated, use a [`HashedProps`] instead. For properties read
+/// on many entities every frame, use a [`PropColumn`].
 #[derive(Component, Resource, Default, Clone, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Resource, Debug, Default, Clone)
+)]
+#[cfg_attr(
+    all(feature = "reflect", feature = "serialize"),
+    reflect(Serialize, Deserialize)
+)]
 pub struct Props {
     properties: BTreeMap<Estr, Value>,
+    versions: BTreeMap<Estr, u64>,
+    /// The dependency values each cached property was computed from.
+    caches: BTreeMap<Estr, Vec<(Estr, Option<Value>)>>,
 }
 
 impl Props {
@@ -817,6 +1366,11 @@ impl Props {
         }
     }
 
+    /// Returns the value of a property, if it is set.
+    pub(crate) fn get_value(&self, name: Estr) -> Option<Value> {
+        self.properties.get(&name).copied()
+    }
+
     /// Returns a mutable reference to a property value. If the propety value is
     /// of the wrong type or not set, a default value of the correct type will
     /// be inserted.
@@ -827,17 +1381,130 @@ impl Props {
         self.properties.entry(name.into()).or_default().as_mut()
     }
 
+    /// Applies a closure to a property value in place. Like
+    /// [`get_mut`][Props::get_mut], a property which is not set or is of the
+    /// wrong type is first replaced with a default value of the correct type.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("hp", 10.0);
+    /// props.modify("hp", |hp: &mut f32| *hp = hp.min(5.0));
+    /// props.modify("shield", |shield: &mut f32| *shield += 2.0);
+    /// assert_eq!(props["hp"], 5.0);
+    /// assert_eq!(props["shield"], 2.0);
+    /// ```
+    pub fn modify<T>(&mut self, name: impl Into<Estr>, f: impl FnOnce(&mut T))
+    where
+        Value: AsMut<T>,
+    {
+        f(self.get_mut(name));
+    }
+
+    /// Returns a property value, first setting it to a default if it is not
+    /// set or is of the wrong type.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("hp", 10.0).with("name", true);
+    /// assert_eq!(props.get_or_insert("hp", 100.0), 10.0);
+    /// assert_eq!(props.get_or_insert("max_hp", 100.0), 100.0);
+    /// assert_eq!(props["max_hp"], 100.0);
+    /// assert_eq!(props.get_or_insert("name", 0.0), 0.0);
+    /// ```
+    pub fn get_or_insert<T>(&mut self, name: impl Into<Estr>, default: T) -> T
+    where
+        T: ValueType + From<Value>,
+    {
+        let default = default.into();
+        let value = self.properties.entry(name.into()).or_insert(default);
+        if value.kind() != T::KIND {
+            *value = default;
+        }
+        (*value).into()
+    }
+
     /// Sets a property value.
     pub fn set(&mut self, name: impl Into<Estr>, value: impl Into<Value>) {
         self.properties.insert(name.into(), value.into());
     }
 
+    /// Sets a property value, unless it is `NaN`. Infinities are clamped to
+    /// `f32::MAX` or `f32::MIN`. This keeps a bad calculation from storing a
+    /// value which is not equal to itself and cannot be sorted.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("speed", 2.0);
+    /// assert!(props.set_checked("speed", 0.0 / 0.0).is_err());
+    /// assert_eq!(props["speed"], 2.0);
+    ///
+    /// props.set_checked("speed", 1.0 / 0.0).unwrap();
+    /// assert_eq!(props["speed"], f32::MAX);
+    /// ```
+    pub fn set_checked(
+        &mut self,
+        name: impl Into<Estr>,
+        value: impl Into<Value>,
+    ) -> Result<(), NanValueError> {
+        let name = name.into();
+        let value = match value.into() {
+            Value::Num(num) if num.is_nan() => return Err(NanValueError { name }),
+            Value::Num(num) => Value::Num(num.clamp(f32::MIN, f32::MAX)),
+            value => value,
+        };
+        self.set(name, value);
+        Ok(())
+    }
+
     /// Sets a property value, and can be chained.
     pub fn with(mut self, name: impl Into<Estr>, value: impl Into<Value>) -> Self {
         self.set(name, value);
         self
     }
 
+    /// Adds to a numeric property and returns the new value. A property which
+    /// is not set or is not a number counts as zero.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("name", "sam");
+    /// assert_eq!(props.increment("score", 10.0), 10.0);
+    /// assert_eq!(props.increment("score", 5.0), 15.0);
+    /// assert_eq!(props.decrement("score", 20.0), -5.0);
+    ///
+    /// assert_eq!(props.increment("name", 1.0), 1.0);
+    /// ```
+    pub fn increment(&mut self, name: impl Into<Estr>, amount: f32) -> f32 {
+        let num = self.get_mut::<f32>(name);
+        *num += amount;
+        *num
+    }
+
+    /// Subtracts from a numeric property and returns the new value. A property
+    /// which is not set or is not a number counts as zero.
+    pub fn decrement(&mut self, name: impl Into<Estr>, amount: f32) -> f32 {
+        self.increment(name, -amount)
+    }
+
+    /// Flips a boolean property. A property which is not set or is not a
+    /// boolean counts as `false`, so toggling it sets it to `true`.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("name", "bilbo");
+    /// props.toggle("wearing_ring");
+    /// assert_eq!(props["wearing_ring"], true);
+    /// props.toggle("wearing_ring");
+    /// assert_eq!(props["wearing_ring"], false);
+    ///
+    /// props.toggle("name");
+    /// assert_eq!(props["name"], true);
+    /// ```
+    pub fn toggle(&mut self, name: impl Into<Estr>) {
+        let flag = self.get_mut::<bool>(name);
+        *flag = !*flag;
+    }
+
     ////Removes a property. Subsiquently accessing this property with `get` or
     /// `get_mut` will return a default value.
     pub fn remove(&mut self, name: impl Into<Estr>) {
@@ -854,6 +1521,78 @@ impl Props {
         self.properties.iter()
     }
 
+    /// Creates a borrowing iterator over all properties whose names start with
+    /// the given prefix. Because properties are sorted by name, this only
+    /// visits matching properties rather than scanning the whole map.
+    ///
+    /// ```rust
+    /// # use bevy_mod_props::prelude::*;
+    /// let props = Props::new()
+    ///     .with("inventory.sword", 1.0)
+    ///     .with("inventory.potion", 3.0)
+    ///     .with("health", 100.0);
+    ///
+    /// assert_eq!(props.iter_prefix("inventory.").count(), 2);
+    /// ```
+    pub fn iter_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a Estr, &'a Value)> {
+        self.properties
+            .range(Estr::from(prefix)..)
+            .take_while(move |(name, _)| name.as_str().starts_with(prefix))
+    }
+
+    /// Creates a borrowing iterator over all properties whose names fall in
+    /// the range `[start, end)`, in order. Like
+    /// [`iter_prefix`][Props::iter_prefix], this only visits matching
+    /// properties.
+    ///
+    /// ```rust
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new();
+    /// for i in 0..300 {
+    ///     props.set(format!("item_{i:04}"), i as f32);
+    /// }
+    ///
+    /// let page: Vec<f32> = props.range("item_0100", "item_0200")
+    ///     .map(|(_, &value)| value.into())
+    ///     .collect();
+    /// assert_eq!(page.len(), 100);
+    /// assert_eq!(page[0], 100.0);
+    /// assert_eq!(page[99], 199.0);
+    /// ```
+    pub fn range<'a>(
+        &'a self,
+        start: &str,
+        end: &'a str,
+    ) -> impl Iterator<Item = (&'a Estr, &'a Value)> {
+        self.properties
+            .range(Estr::from(start)..)
+            .take_while(move |(name, _)| name.as_str() < end)
+    }
+
+    /// Like [`iter_prefix`][Props::iter_prefix], but yields property names
+    /// with the prefix removed. This is handy for building sub-views of a
+    /// namespace.
+    ///
+    /// ```rust
+    /// # use bevy_mod_props::prelude::*;
+    /// let props = Props::new()
+    ///     .with("inventory.sword", 1.0)
+    ///     .with("health", 100.0);
+    ///
+    /// let (name, _) = props.strip_prefix("inventory.").next().unwrap();
+    /// assert_eq!(name, "sword");
+    /// ```
+    pub fn strip_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a Value)> {
+        self.iter_prefix(prefix)
+            .map(move |(name, value)| (&name.as_str()[prefix.len()..], value))
+    }
+
     /// Creates a borrowing iterator over property names.
     pub fn keys(&self) -> Keys<'_, Estr, Value> {
         self.properties.keys()
@@ -878,6 +1617,64 @@ impl Props {
     pub fn values_mut(&mut self) -> ValuesMut<'_, Estr, Value> {
         self.properties.values_mut()
     }
+
+    /// Merges another set of properties into this one. Properties only set in
+    /// `other` are copied over, and for properties set in both, `resolve` is
+    /// called with the name, this value, and the other value to decide the
+    /// result.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("hp", 80.0).with("title", "squire");
+    /// let other = Props::new().with("hp", 50.0).with("title", "knight").with("xp", 10.0);
+    ///
+    /// props.merge_with(&other, |_, ours, theirs| match (ours, theirs) {
+    ///     (Value::Num(a), Value::Num(b)) => Value::Num(a.max(b)),
+    ///     (_, theirs) => theirs,
+    /// });
+    ///
+    /// assert_eq!(props["hp"], 80.0);
+    /// assert_eq!(props["title"], "knight");
+    /// assert_eq!(props["xp"], 10.0);
+    /// ```
+    pub fn merge_with(
+        &mut self,
+        other: &Props,
+        mut resolve: impl FnMut(&Estr, Value, Value) -> Value,
+    ) {
+        for (name, &theirs) in &other.properties {
+            match self.properties.entry(*name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(theirs);
+                }
+                Entry::Occupied(mut entry) => {
+                    let ours = *entry.get();
+                    entry.insert(resolve(name, ours, theirs));
+                }
+            }
+        }
+    }
+}
+
+/// Creates [`Props`] from a list of names and values.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// let props = props! {
+///     "hp" => 100.0,
+///     "name" => "bob",
+///     "alive" => true,
+/// };
+///
+/// assert_eq!(props["hp"], 100.0);
+/// assert_eq!(props["name"], "bob");
+/// assert_eq!(props["alive"], true);
+/// ```
+#[macro_export]
+macro_rules! props {
+    ($($name:expr => $value:expr),* $(,)?) => {
+        $crate::props::Props::new()$(.with($name, $value))*
+    };
 }
 
 static DEFAULT_VALUE: LazyLock<Value> = LazyLock::new(Value::default);
@@ -904,3 +1701,307 @@ impl IntoIterator for Props {
         self.properties.into_iter()
     }
 }
+
+// -----------------------------------------------------------------------------
+// Map Conversions
+
+impl Props {
+    /// Creates properties from a map of names to values, interning each name.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut config = HashMap::new();
+    /// config.insert("volume".to_string(), Value::Num(0.5));
+    /// config.insert("fullscreen".to_string(), Value::Bool(true));
+    ///
+    /// let props = Props::from_map(config.clone());
+    /// assert_eq!(props["volume"], 0.5);
+    /// assert_eq!(props.to_map(), config);
+    /// ```
+    pub fn from_map(map: HashMap<String, Value>) -> Props {
+        let mut props = Props::new();
+        for (name, value) in map {
+            props.set(name, value);
+        }
+        props
+    }
+
+    /// Copies the properties into a map of names to values.
+    pub fn to_map(&self) -> HashMap<String, Value> {
+        self.properties
+            .iter()
+            .map(|(name, &value)| (name.to_string(), value))
+            .collect()
+    }
+}
+
+impl From<HashMap<String, Value>> for Props {
+    fn from(map: HashMap<String, Value>) -> Props {
+        Props::from_map(map)
+    }
+}
+
+impl From<Props> for HashMap<String, Value> {
+    fn from(props: Props) -> HashMap<String, Value> {
+        props.to_map()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Prop Entities
+
+/// Marks an entity that should always have [`Props`] and
+/// [`Links`][crate::links::Links] components.
+///
+/// Props and links are usually added on demand, so an entity without them
+/// behaves as if they were empty. Spawning this marker inserts both up front
+/// through bevy's required components, so they can be queried directly.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let orc = world.spawn(PropEntity).id();
+///
+/// assert!(world.entity(orc).contains::<Props>());
+/// assert!(world.entity(orc).contains::<Links>());
+/// ```
+#[derive(Component, Default, Copy, Clone, Debug)]
+#[require(Props, crate::links::Links)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, Clone)
+)]
+pub struct PropEntity;
+
+// -----------------------------------------------------------------------------
+// Struct Conversions
+
+/// Converts a type to and from [`Props`].
+///
+/// With the `derive` feature, this can be derived for structs with named
+/// fields. Each field is stored as a property with the same name, using the
+/// field type's conversions to and from [`Value`], so every field type must
+/// implement `Clone`, `Into<Value>`, `From<Value>`, and `Default`. Fields of
+/// any other type are a compile error. Missing or mistyped properties become
+/// the default value of the field.
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+```
+# use bevy_mod_props::prelude::*;
+#[derive(IntoProps, PartialEq, Debug)]
+struct Stats {
+    hp: f32,
+    name: String,
+    alive: bool,
+}
+
+let stats = Stats { hp: 80.0, name: "bob".into(), alive: true };
+let props = stats.to_props();
+assert_eq!(props["hp"], 80.0);
+assert_eq!(Stats::from_props(&props), stats);
+```
+
+```compile_fail
+# use bevy_mod_props::prelude::*;
+#[derive(IntoProps)]
+struct Inventory {
+    items: Vec<String>,
+}
+```
+"#
+)]
+pub trait IntoProps: Sized {
+    /// Stores this value as properties.
+    fn to_props(&self) -> Props;
+
+    /// Reads a value back from properties.
+    fn from_props(props: &Props) -> Self;
+}
+
+// -----------------------------------------------------------------------------
+// Caching
+
+impl Props {
+    /// Returns a cached property, computing and storing it if it is not set or
+    /// if any of the properties in `invalidated_by` have changed since it was
+    /// last computed. This is a manual memoization helper for values that are
+    /// expensive to derive.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("strength", 10.0);
+    /// let mut runs = 0;
+    /// let mut carry_weight = |props: &mut Props| {
+    ///     let strength: f32 = props.get("strength");
+    ///     props.cached_get("carry_weight", || { runs += 1; (strength * 5.0).into() }, &["strength"])
+    /// };
+    ///
+    /// assert_eq!(carry_weight(&mut props), 50.0);
+    /// assert_eq!(carry_weight(&mut props), 50.0);
+    /// props.set("strength", 12.0);
+    /// assert_eq!(carry_weight(&mut props), 60.0);
+    /// assert_eq!(runs, 2);
+    /// ```
+    pub fn cached_get(
+        &mut self,
+        name: impl Into<Estr>,
+        compute: impl FnOnce() -> Value,
+        invalidated_by: &[&str],
+    ) -> Value {
+        let name = name.into();
+        let dependencies: Vec<_> = invalidated_by
+            .iter()
+            .map(|&dependency| {
+                let dependency = Estr::from(dependency);
+                (dependency, self.get_value(dependency))
+            })
+            .collect();
+        if let Some(value) = self.get_value(name)
+            && self.caches.get(&name) == Some(&dependencies)
+        {
+            return value;
+        }
+        let value = compute();
+        self.properties.insert(name, value);
+        self.caches.insert(name, dependencies);
+        value
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Key Arithmetic
+
+impl Props {
+    /// Stores `a + b` in `dest`, and returns it. The operands follow the
+    /// [math rules][Value#math] for values, so properties which are missing or
+    /// are not numbers act like zero.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new().with("gross", 100.0).with("tax", 20.0);
+    /// props.sub_keys("net", "gross", "tax");
+    /// assert_eq!(props["net"], 80.0);
+    ///
+    /// assert_eq!(props.add_keys("total", "gross", "bonus"), 100.0);
+    /// assert_eq!(props.mul_keys("area", "net", "tax"), 1600.0);
+    /// assert_eq!(props.div_keys("rate", "tax", "gross"), 0.2);
+    /// ```
+    pub fn add_keys(
+        &mut self,
+        dest: impl Into<Estr>,
+        a: impl Into<Estr>,
+        b: impl Into<Estr>,
+    ) -> f32 {
+        self.combine_keys(dest, self[a] + self[b])
+    }
+
+    /// Stores `a - b` in `dest`, and returns it. See [`add_keys`][Props::add_keys].
+    pub fn sub_keys(
+        &mut self,
+        dest: impl Into<Estr>,
+        a: impl Into<Estr>,
+        b: impl Into<Estr>,
+    ) -> f32 {
+        self.combine_keys(dest, self[a] - self[b])
+    }
+
+    /// Stores `a * b` in `dest`, and returns it. See [`add_keys`][Props::add_keys].
+    pub fn mul_keys(
+        &mut self,
+        dest: impl Into<Estr>,
+        a: impl Into<Estr>,
+        b: impl Into<Estr>,
+    ) -> f32 {
+        self.combine_keys(dest, self[a] * self[b])
+    }
+
+    /// Stores `a / b` in `dest`, and returns it. If `b` is not a number, the
+    /// result is `a`. See [`add_keys`][Props::add_keys].
+    pub fn div_keys(
+        &mut self,
+        dest: impl Into<Estr>,
+        a: impl Into<Estr>,
+        b: impl Into<Estr>,
+    ) -> f32 {
+        self.combine_keys(dest, self[a] / self[b])
+    }
+
+    fn combine_keys(&mut self, dest: impl Into<Estr>, result: Value) -> f32 {
+        self.set(dest, result);
+        result.into()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Operations
+
+/// A single change to a set of properties. Useful for sending property deltas
+/// over the network.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PropOp {
+    /// Sets a property to a value.
+    Set(Estr, Value),
+    /// Removes a property.
+    Remove(Estr),
+}
+
+impl PropOp {
+    /// Returns the name of the property this operation changes.
+    pub fn name(&self) -> Estr {
+        match self {
+            PropOp::Set(name, _) | PropOp::Remove(name) => *name,
+        }
+    }
+}
+
+impl Props {
+    /// Applies a single operation.
+    pub fn apply(&mut self, op: PropOp) {
+        match op {
+            PropOp::Set(name, value) => self.set(name, value),
+            PropOp::Remove(name) => self.remove(name),
+        }
+    }
+
+    /// Applies a list of versioned operations. Each operation is applied only
+    /// if its version is greater than the last version applied to the same
+    /// property, so the result does not depend on the order the operations
+    /// arrive in, and applying the same operations twice has no effect.
+    ///
+    /// ```
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut props = Props::new();
+    /// props.apply_ops_versioned(&[
+    ///     (2, PropOp::Set("hp".into(), 50.0.into())),
+    ///     (1, PropOp::Set("hp".into(), 100.0.into())),
+    ///     (1, PropOp::Set("mood".into(), "calm".into())),
+    /// ]);
+    /// props.apply_ops_versioned(&[
+    ///     (3, PropOp::Set("mood".into(), "angry".into())),
+    ///     (2, PropOp::Remove("mood".into())),
+    /// ]);
+    ///
+    /// assert_eq!(props["hp"], 50.0);
+    /// assert_eq!(props["mood"], "angry");
+    /// assert_eq!(props.version("hp"), Some(2));
+    /// ```
+    pub fn apply_ops_versioned(&mut self, ops: &[(u64, PropOp)]) {
+        for &(version, op) in ops {
+            if self.version(op.name()).is_some_and(|last| version <= last) {
+                continue;
+            }
+            self.versions.insert(op.name(), version);
+            self.apply(op);
+        }
+    }
+
+    /// Returns the version of the last versioned operation applied to a
+    /// property, if any.
+    pub fn version(&self, name: impl Into<Estr>) -> Option<u64> {
+        self.versions.get(&name.into()).copied()
+    }
+}
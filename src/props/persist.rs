@@ -0,0 +1,128 @@
+//! Mirrors the global props resource to a file on disk, behind the `persist`
+//! feature.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use bevy_app::AppExit;
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::message::MessageReader;
+use bevy_ecs::resource::Resource;
+use bevy_ecs::system::{Res, ResMut};
+use estr::Estr;
+use ron::ser::PrettyConfig;
+
+use super::{Props, Value};
+
+/// Configures saving the global [`Props`] resource to a RON file.
+///
+/// This is inserted by
+/// [`persist_global_props`][crate::plugin::PropsPlugin::persist_global_props],
+/// and used by [`save_global_props`] and [`flush_global_props`].
+#[derive(Resource, Clone, Debug)]
+pub struct PersistGlobalProps {
+    path: PathBuf,
+    /// How long the props must go unchanged before they are written.
+    /// Defaults to one second.
+    pub debounce: Duration,
+    last_change: Option<Instant>,
+    /// The values last loaded from or written to the file.
+    saved: BTreeMap<Estr, Value>,
+}
+
+impl PersistGlobalProps {
+    /// Creates a new configuration that saves to the given path.
+    pub fn new(path: impl Into<PathBuf>) -> PersistGlobalProps {
+        PersistGlobalProps {
+            path: path.into(),
+            debounce: Duration::from_secs(1),
+            last_change: None,
+            saved: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a new configuration that saves to the given path, where the
+    /// file already holds the given props. They are only written once they
+    /// differ from these.
+    pub fn loaded(path: impl Into<PathBuf>, props: &Props) -> PersistGlobalProps {
+        PersistGlobalProps {
+            saved: props.properties.clone(),
+            ..PersistGlobalProps::new(path)
+        }
+    }
+
+    /// Returns the path the props are saved to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Loads props from a RON file. A missing file produces empty props, and an
+/// unreadable or corrupt file produces empty props and logs a warning.
+pub fn load_global_props(path: impl AsRef<Path>) -> Props {
+    let path = path.as_ref();
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Props::default(),
+        Err(error) => {
+            log::warn!("failed to read props from {}: {error}", path.display());
+            return Props::default();
+        }
+    };
+    match ron::from_str(&text) {
+        Ok(props) => props,
+        Err(error) => {
+            log::warn!("failed to parse props from {}: {error}", path.display());
+            Props::default()
+        }
+    }
+}
+
+/// Writes the global [`Props`] resource to disk once it has gone unchanged
+/// for the configured [`debounce`][PersistGlobalProps::debounce] period.
+///
+/// Props are compared with the values last loaded or written, so changes made
+/// before this first runs (such as in `Startup`) are saved, and the props
+/// loaded from the file are not written straight back.
+pub fn save_global_props(props: Res<Props>, mut persist: ResMut<PersistGlobalProps>) {
+    if props.is_changed() && props.properties != persist.saved {
+        persist.last_change = Some(Instant::now());
+    }
+    let Some(last_change) = persist.last_change else {
+        return;
+    };
+    if last_change.elapsed() < persist.debounce {
+        return;
+    }
+    write_global_props(&props, &mut persist);
+}
+
+/// Writes the global [`Props`] resource to disk straight away when the app
+/// exits, if it has changed since it was last written, so changes still
+/// waiting out the debounce period are not lost.
+pub fn flush_global_props(
+    mut exits: MessageReader<AppExit>,
+    props: Res<Props>,
+    mut persist: ResMut<PersistGlobalProps>,
+) {
+    if exits.read().count() > 0 && props.properties != persist.saved {
+        write_global_props(&props, &mut persist);
+    }
+}
+
+fn write_global_props(props: &Props, persist: &mut PersistGlobalProps) {
+    persist.last_change = None;
+    let result = ron::ser::to_string_pretty(props, PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|text| fs::write(&persist.path, text).map_err(|error| error.to_string()));
+    match result {
+        Ok(()) => persist.saved = props.properties.clone(),
+        Err(error) => log::warn!(
+            "failed to write props to {}: {error}",
+            persist.path.display()
+        ),
+    }
+}
@@ -0,0 +1,84 @@
+//! Contains per-property defaults, used in place of type defaults
+
+use bevy_ecs::resource::Resource;
+use estr::{Estr, EstrMap};
+
+use super::{Props, Value};
+
+// -----------------------------------------------------------------------------
+// Prop Schema
+
+/// Registers default values for properties which are not set.
+///
+/// Reading a missing property normally returns the default of the requested
+/// type, so a missing `"health"` read as an `f32` is `0.0`. With a schema, it
+/// can be `100.0` instead. When a property is read, the first of these is
+/// used:
+///
+/// 1. The value of the property, if it is set.
+/// 2. The default registered in the schema.
+/// 3. The default of the requested type.
+///
+/// The schema is only consulted when it is passed in explicitly, with
+/// [`Props::get_or_schema`] or
+/// [`get_prop_or_schema`][super::PropsExt::get_prop_or_schema].
+/// [`get_prop`][super::PropsExt::get_prop] and [`Props::get`] always fall
+/// back to the type default.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut schema = PropSchema::default();
+/// schema.register_default("health", 100.0);
+///
+/// let mut world = World::new();
+/// world.insert_resource(schema);
+/// let hero = world.spawn_empty().id();
+///
+/// let schema = world.resource::<PropSchema>();
+/// let hero_ref = world.entity(hero);
+/// assert_eq!(hero_ref.get_prop_or_schema::<f32>("health", schema), 100.0);
+/// assert_eq!(hero_ref.get_prop_or_schema::<f32>("mana", schema), 0.0);
+/// assert_eq!(hero_ref.get_prop::<f32>("health"), 0.0);
+///
+/// world.entity_mut(hero).set_prop("health", 20.0);
+/// let schema = world.resource::<PropSchema>();
+/// assert_eq!(world.entity(hero).get_prop_or_schema::<f32>("health", schema), 20.0);
+/// ```
+#[derive(Resource, Default, Clone, Debug)]
+pub struct PropSchema {
+    defaults: EstrMap<Value>,
+}
+
+impl PropSchema {
+    /// Registers the default value of a property, replacing any previous
+    /// default.
+    pub fn register_default(&mut self, name: impl Into<Estr>, value: impl Into<Value>) {
+        self.defaults.insert(name.into(), value.into());
+    }
+
+    /// Removes the default value of a property.
+    pub fn unregister_default(&mut self, name: impl Into<Estr>) {
+        self.defaults.remove(&name.into());
+    }
+
+    /// Returns the registered default value of a property.
+    pub fn default_of(&self, name: impl Into<Estr>) -> Option<Value> {
+        self.defaults.get(&name.into()).copied()
+    }
+}
+
+impl Props {
+    /// Returns a property value, falling back to the default registered in
+    /// the schema and then to the default of the type. See [`PropSchema`].
+    pub fn get_or_schema<T>(&self, name: impl Into<Estr>, schema: &PropSchema) -> T
+    where
+        T: From<Value> + Default + 'static,
+    {
+        let name = name.into();
+        match self.get_value(name).or_else(|| schema.default_of(name)) {
+            Some(value) => value.into(),
+            None => T::default(),
+        }
+    }
+}
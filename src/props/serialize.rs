@@ -0,0 +1,153 @@
+//! Implements serde support for props, behind the `serialize` feature.
+//!
+//! Values are serialized as plain booleans, numbers, or strings, and property
+//! maps as maps from names to values. This keeps saved props readable in
+//! formats like RON and JSON.
+
+use std::fmt;
+
+use estr::Estr;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::{Props, Value, ValueKind};
+
+// -----------------------------------------------------------------------------
+// Values
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Num(value) => serializer.serialize_f32(*value),
+            Value::Str(value) => serializer.serialize_str(value.as_str()),
+        }
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean, number, or string")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Num(value as f32))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::Num(value as f32))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Num(value as f32))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::Str(Estr::from(value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Kinds
+
+/// Kinds are serialized by name, as `"bool"`, `"num"`, or `"str"`. Together
+/// with [`Props::schema_of`], this can be used to save the shape of a set of
+/// properties.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use bevy_mod_props::prelude::*;
+/// let props = Props::new().with("alive", true).with("hp", 10.0).with("name", "sam");
+/// let schema: BTreeMap<&str, ValueKind> = props
+///     .schema_of()
+///     .into_iter()
+///     .map(|(name, kind)| (name.as_str(), kind))
+///     .collect();
+///
+/// let text = ron::to_string(&schema).unwrap();
+/// assert_eq!(text, r#"{"alive":"bool","hp":"num","name":"str"}"#);
+///
+/// let loaded: BTreeMap<&str, ValueKind> = ron::from_str(&text).unwrap();
+/// assert_eq!(loaded, schema);
+/// ```
+impl Serialize for ValueKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct ValueKindVisitor;
+
+impl<'de> Visitor<'de> for ValueKindVisitor {
+    type Value = ValueKind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("one of \"bool\", \"num\", or \"str\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<ValueKind, E> {
+        match value {
+            "bool" => Ok(ValueKind::Bool),
+            "num" => Ok(ValueKind::Num),
+            "str" => Ok(ValueKind::Str),
+            _ => Err(E::unknown_variant(value, &["bool", "num", "str"])),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ValueKindVisitor)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Property Maps
+
+impl Serialize for Props {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.properties.len()))?;
+        for (name, value) in &self.properties {
+            map.serialize_entry(name.as_str(), value)?;
+        }
+        map.end()
+    }
+}
+
+struct PropsVisitor;
+
+impl<'de> Visitor<'de> for PropsVisitor {
+    type Value = Props;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of property names to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Props, A::Error> {
+        let mut props = Props::new();
+        while let Some((name, value)) = access.next_entry::<String, Value>()? {
+            props.set(name, value);
+        }
+        Ok(props)
+    }
+}
+
+impl<'de> Deserialize<'de> for Props {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(PropsVisitor)
+    }
+}
@@ -0,0 +1,130 @@
+//! Contains snapshots and deltas of property maps, for undo and redo
+
+use std::collections::BTreeMap;
+
+use estr::Estr;
+
+use super::{Props, Value};
+
+// -----------------------------------------------------------------------------
+// Snapshots
+
+/// A saved copy of the values in a [`Props`], created with
+/// [`Props::snapshot`] and put back with [`Props::restore`].
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// let mut props = Props::new().with("x", 1.0);
+/// let before = props.snapshot();
+/// props.set("x", 5.0);
+/// props.set("locked", true);
+///
+/// props.restore(before);
+/// assert_eq!(props["x"], 1.0);
+/// assert!(props.keys().all(|name| name.as_str() != "locked"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PropsSnapshot {
+    properties: BTreeMap<Estr, Value>,
+}
+
+/// The changes made to a [`Props`] since a [`PropsSnapshot`] was taken,
+/// created with [`Props::delta_since`].
+///
+/// A delta records both the old and new value of each changed property, so
+/// it can be applied with [`Props::apply_delta`] to redo the changes, or with
+/// [`Props::apply_inverse`] to undo them. This is usually much smaller than
+/// storing a snapshot for each step.
+///
+/// ```
+/// # use bevy_mod_props::prelude::*;
+/// let mut props = Props::new().with("x", 1.0).with("y", 2.0);
+/// let before = props.snapshot();
+/// props.set("x", 5.0);
+/// props.remove("y");
+/// props.set("locked", true);
+/// let step = props.delta_since(&before);
+/// assert_eq!(step.len(), 3);
+///
+/// props.apply_inverse(&step);
+/// assert_eq!(props["x"], 1.0);
+/// assert_eq!(props["y"], 2.0);
+/// assert!(props.keys().all(|name| name.as_str() != "locked"));
+///
+/// props.apply_delta(&step);
+/// assert_eq!(props["x"], 5.0);
+/// assert!(props.keys().all(|name| name.as_str() != "y"));
+/// assert_eq!(props["locked"], true);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PropsDelta {
+    /// Each changed property, with its value before and after the change.
+    changes: Vec<(Estr, Option<Value>, Option<Value>)>,
+}
+
+impl PropsDelta {
+    /// Returns the number of changed properties.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns true if no properties were changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Props {
+    /// Saves a copy of the current values. See [`PropsSnapshot`].
+    pub fn snapshot(&self) -> PropsSnapshot {
+        PropsSnapshot {
+            properties: self.properties.clone(),
+        }
+    }
+
+    /// Replaces every value with the values saved in a snapshot.
+    pub fn restore(&mut self, snapshot: PropsSnapshot) {
+        self.properties = snapshot.properties;
+    }
+
+    /// Returns the changes made since a snapshot was taken. See
+    /// [`PropsDelta`].
+    pub fn delta_since(&self, snapshot: &PropsSnapshot) -> PropsDelta {
+        let mut changes = Vec::new();
+        for (&name, &old) in &snapshot.properties {
+            match self.properties.get(&name) {
+                Some(&new) if new == old => {}
+                new => changes.push((name, Some(old), new.copied())),
+            }
+        }
+        for (&name, &new) in &self.properties {
+            if !snapshot.properties.contains_key(&name) {
+                changes.push((name, None, Some(new)));
+            }
+        }
+        PropsDelta { changes }
+    }
+
+    /// Sets or removes each property changed by a delta, to its value after
+    /// the change.
+    pub fn apply_delta(&mut self, delta: &PropsDelta) {
+        for &(name, _, new) in &delta.changes {
+            self.set_or_remove(name, new);
+        }
+    }
+
+    /// Sets or removes each property changed by a delta, to its value before
+    /// the change.
+    pub fn apply_inverse(&mut self, delta: &PropsDelta) {
+        for &(name, old, _) in &delta.changes {
+            self.set_or_remove(name, old);
+        }
+    }
+
+    fn set_or_remove(&mut self, name: Estr, value: Option<Value>) {
+        match value {
+            Some(value) => self.set(name, value),
+            None => self.remove(name),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Defines messages sent when entity names and classes change.
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use bevy_app::App;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    lifecycle::{Insert, Remove, Replace},
+    message::{Message, MessageReader, MessageWriter},
+    observer::On,
+    resource::Resource,
+    system::{Query, ResMut},
+};
+use estr::Estr;
+
+use super::{Class, Identity};
+
+/// Sent when an entity is given an [`Identity`] for the first time.
+///
+/// This and the other registry messages are only sent when enabled with
+/// [`PropsPlugin::change_messages`][crate::plugin::PropsPlugin::change_messages].
+#[derive(Message, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NameAssigned {
+    pub entity: Entity,
+    pub name: Estr,
+}
+
+/// Sent when an entity that already had an [`Identity`] is renamed.
+#[derive(Message, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NameChanged {
+    pub entity: Entity,
+    pub old: Estr,
+    pub new: Estr,
+}
+
+/// Sent when an entity's [`Identity`] is removed, including when the entity is
+/// despawned.
+#[derive(Message, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NameRemoved {
+    pub entity: Entity,
+    pub name: Estr,
+}
+
+/// Sent when an entity's [`Class`] is set, changed, or removed (including
+/// when the entity is despawned).
+#[derive(Message, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ClassChanged {
+    pub entity: Entity,
+    pub old: Option<Estr>,
+    pub new: Option<Estr>,
+}
+
+/// A run condition that is true when an entity joined a class since the
+/// condition last ran. An entity joins a class when it is given that class as
+/// its [`Class`], either for the first time or in place of another class.
+///
+/// This reads [`ClassChanged`] messages, so they must be enabled with
+/// [`PropsPlugin::change_messages`][crate::plugin::PropsPlugin::change_messages].
+/// Classes added through [`Classes`][super::Classes] are not counted.
+///
+/// ```
+/// # use bevy_app::{App, Update};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Setups(u32);
+///
+/// let mut app = App::new();
+/// app.add_plugins(PropsPlugin::default().change_messages())
+///     .init_resource::<Setups>()
+///     .add_systems(
+///         Update,
+///         (|mut setups: ResMut<Setups>| setups.0 += 1).run_if(any_new_class("zombie")),
+///     );
+///
+/// app.update();
+/// app.world_mut().spawn(Class::new("zombie"));
+/// app.update();
+/// app.update();
+/// assert_eq!(app.world().resource::<Setups>().0, 1);
+/// ```
+pub fn any_new_class(
+    class: impl Into<Estr>,
+) -> impl FnMut(MessageReader<ClassChanged>) -> bool + Clone {
+    let class = class.into();
+    move |mut messages: MessageReader<ClassChanged>| {
+        // Read every message so they aren't seen again next time
+        messages
+            .read()
+            .filter(|message| message.new == Some(class) && message.old != Some(class))
+            .count()
+            > 0
+    }
+}
+
+/// Values of a component that are in the middle of being replaced.
+#[derive(Resource)]
+struct Replaced<C> {
+    values: EntityHashMap<Estr>,
+    _component: PhantomData<C>,
+}
+
+impl<C> Default for Replaced<C> {
+    fn default() -> Self {
+        Replaced {
+            values: EntityHashMap::default(),
+            _component: PhantomData,
+        }
+    }
+}
+
+/// Records the value of a component before it is replaced or removed.
+fn on_replace<C: Component + Deref<Target = Estr>>(
+    replace: On<Replace, C>,
+    components: Query<&C>,
+    mut replaced: ResMut<Replaced<C>>,
+) {
+    if let Ok(component) = components.get(replace.entity) {
+        replaced.values.insert(replace.entity, **component);
+    }
+}
+
+fn on_insert_identity(
+    insert: On<Insert, Identity>,
+    identities: Query<&Identity>,
+    mut replaced: ResMut<Replaced<Identity>>,
+    mut assigned: MessageWriter<NameAssigned>,
+    mut changed: MessageWriter<NameChanged>,
+) {
+    let entity = insert.entity;
+    let Ok(&Identity(new)) = identities.get(entity) else {
+        return;
+    };
+    match replaced.values.remove(&entity) {
+        None => {
+            assigned.write(NameAssigned { entity, name: new });
+        }
+        Some(old) if old != new => {
+            changed.write(NameChanged { entity, old, new });
+        }
+        Some(_) => {}
+    }
+}
+
+fn on_remove_identity(
+    remove: On<Remove, Identity>,
+    mut replaced: ResMut<Replaced<Identity>>,
+    mut removed: MessageWriter<NameRemoved>,
+) {
+    if let Some(name) = replaced.values.remove(&remove.entity) {
+        removed.write(NameRemoved {
+            entity: remove.entity,
+            name,
+        });
+    }
+}
+
+fn on_insert_class(
+    insert: On<Insert, Class>,
+    classes: Query<&Class>,
+    mut replaced: ResMut<Replaced<Class>>,
+    mut messages: MessageWriter<ClassChanged>,
+) {
+    let old = replaced.values.remove(&insert.entity);
+    let new = classes.get(insert.entity).ok().map(|class| class.0);
+    messages.write(ClassChanged {
+        entity: insert.entity,
+        old,
+        new,
+    });
+}
+
+fn on_remove_class(
+    remove: On<Remove, Class>,
+    mut replaced: ResMut<Replaced<Class>>,
+    mut messages: MessageWriter<ClassChanged>,
+) {
+    let old = replaced.values.remove(&remove.entity);
+    messages.write(ClassChanged {
+        entity: remove.entity,
+        old,
+        new: None,
+    });
+}
+
+/// Sends the registry's name and class messages.
+pub(crate) fn send_change_messages(app: &mut App) {
+    app.add_message::<NameAssigned>()
+        .add_message::<NameChanged>()
+        .add_message::<NameRemoved>()
+        .add_message::<ClassChanged>()
+        .init_resource::<Replaced<Identity>>()
+        .init_resource::<Replaced<Class>>()
+        .add_observer(on_replace::<Identity>)
+        .add_observer(on_insert_identity)
+        .add_observer(on_remove_identity)
+        .add_observer(on_replace::<Class>)
+        .add_observer(on_insert_class)
+        .add_observer(on_remove_class);
+}
@@ -1,8 +1,9 @@
 //! Defines extension traits for using the registry with bevy
 
 use bevy_ecs::{
-    entity::{Entity, EntityHashSet, EntityNotSpawnedError},
-    system::EntityCommands,
+    entity::{hash_set, Entity, EntityHashSet, EntityNotSpawnedError},
+    error::ErrorContext,
+    system::{Commands, EntityCommands},
     world::{
         error::EntityMutableFetchError, unsafe_world_cell::UnsafeWorldCell, DeferredWorld,
         EntityMut, EntityRef, EntityWorldMut, World, WorldEntityFetch,
@@ -11,7 +12,11 @@ use bevy_ecs::{
 use estr::Estr;
 use thiserror::Error;
 
-use super::{Class, EntityNotFoundError, Identity, Registry, EMPTY_SET};
+use super::{
+    AliasTakenError, Aliases, Class, ClassDefaults, ClassHierarchy, Classes, EntityNotFoundError,
+    Identity, NameTakenError, Registry, RegistryMissingError, ReservedNameError, EMPTY_SET,
+};
+use crate::props::{Props, Value};
 
 // -----------------------------------------------------------------------------
 // Registry Access
@@ -20,6 +25,13 @@ pub trait RegistryExt {
     fn get_name(&self) -> Option<Estr>;
 
     fn get_class(&self) -> Option<Estr>;
+
+    /// Returns true if this entity has the class, either as its [`Class`] or
+    /// among its [`Classes`].
+    fn has_class(&self, class: impl Into<Estr>) -> bool;
+
+    /// Returns this entity's [`Aliases`], sorted.
+    fn get_aliases(&self) -> Vec<Estr>;
 }
 
 impl<'w> RegistryExt for EntityRef<'w> {
@@ -30,35 +42,539 @@ impl<'w> RegistryExt for EntityRef<'w> {
     fn get_class(&self) -> Option<Estr> {
         self.get::<Class>().map(|i| i.0)
     }
+
+    fn has_class(&self, class: impl Into<Estr>) -> bool {
+        let class = class.into();
+        self.get::<Class>().is_some_and(|c| c.0 == class)
+            || self.get::<Classes>().is_some_and(|c| c.contains(&class))
+    }
+
+    fn get_aliases(&self) -> Vec<Estr> {
+        let mut aliases: Vec<_> = self
+            .get::<Aliases>()
+            .into_iter()
+            .flat_map(|aliases| aliases.iter().copied())
+            .collect();
+        aliases.sort_by_key(|alias| alias.as_str());
+        aliases
+    }
 }
 
 // -----------------------------------------------------------------------------
 // Registry Mutation
 
 pub trait RegistryCommandsExt {
+    /// Names this entity. If the name uses one of the registry's
+    /// [reserved prefixes][super::ReservedNamePrefixes], the name is not set
+    /// and a [`ReservedNameError`] is passed to the default error handler.
+    ///
+    /// Naming an entity that already has a name renames it: the old name is
+    /// removed from the registry before the new one is added.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let wizard = world.spawn_empty().set_name("gandalf_the_grey").id();
+    /// world.entity_mut(wizard).set_name("gandalf_the_white");
+    ///
+    /// assert!(world.lookup_name("gandalf_the_grey").is_err());
+    /// assert_eq!(world.lookup_name("gandalf_the_white").unwrap(), wizard);
+    /// ```
     fn set_name(&mut self, name: impl Into<Estr>) -> &mut Self;
 
     fn set_class(&mut self, class: impl Into<Estr>) -> &mut Self;
+
+    /// Sets the name and class of this entity in a single operation.
+    ///
+    /// Both components are inserted together, so the registry's name and
+    /// class indices are both updated before any observer runs. Like
+    /// [`set_name`][RegistryCommandsExt::set_name], reserved names are
+    /// rejected (and in that case the class is not set either).
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.add_observer(|insert: On<Insert, Identity>, registry: Res<Registry>| {
+    ///     assert!(registry.lookup_class("wizard").contains(&insert.entity));
+    /// });
+    ///
+    /// let gandalf = world.spawn_empty().set_identity("gandalf", "wizard").id();
+    /// assert_eq!(world.lookup_name("gandalf").unwrap(), gandalf);
+    /// ```
+    fn set_identity(&mut self, name: impl Into<Estr>, class: impl Into<Estr>) -> &mut Self;
+
+    /// Adds a class to this entity's [`Classes`].
+    fn add_class(&mut self, class: impl Into<Estr>) -> &mut Self;
+
+    /// Removes a class from this entity's [`Classes`]. This does not affect
+    /// the entity's [`Class`].
+    fn remove_class(&mut self, class: impl Into<Estr>) -> &mut Self;
+
+    /// Adds an alias to this entity's [`Aliases`]. If the alias is already
+    /// used by another entity, or uses a reserved prefix, it is not added and
+    /// an error is passed to the default error handler.
+    fn add_alias(&mut self, alias: impl Into<Estr>) -> &mut Self;
+
+    /// Removes an alias from this entity's [`Aliases`]. This does not affect
+    /// the entity's [`Identity`].
+    fn remove_alias(&mut self, alias: impl Into<Estr>) -> &mut Self;
+}
+
+/// Applies the registry's normalizer to a name, if there is a registry.
+fn normalize_name(entity: &EntityWorldMut, name: impl Into<Estr>) -> Estr {
+    match entity.world().get_resource::<Registry>() {
+        Some(registry) => registry.normalize(name),
+        None => name.into(),
+    }
+}
+
+/// Reports an error and returns true if the name uses a reserved prefix.
+fn reject_reserved_name(entity: &EntityWorldMut, name: Estr, command: &'static str) -> bool {
+    let world = entity.world();
+    let Some(registry) = world.get_resource::<Registry>() else {
+        return false;
+    };
+    if !registry.reserved_prefixes().is_reserved(&name) {
+        return false;
+    }
+    let error_handler = world.default_error_handler();
+    error_handler(
+        ReservedNameError {
+            name,
+            requester: entity.id(),
+        }
+        .into(),
+        ErrorContext::Command {
+            name: command.into(),
+        },
+    );
+    true
 }
 
 impl<'w> RegistryCommandsExt for EntityWorldMut<'w> {
     fn set_name(&mut self, name: impl Into<Estr>) -> &mut Self {
+        let name = normalize_name(self, name);
+        if reject_reserved_name(self, name, "set_name") {
+            return self;
+        }
         self.insert(Identity::new(name))
     }
 
     fn set_class(&mut self, class: impl Into<Estr>) -> &mut Self {
         self.insert(Class::new(class))
     }
+
+    fn set_identity(&mut self, name: impl Into<Estr>, class: impl Into<Estr>) -> &mut Self {
+        let name = normalize_name(self, name);
+        if reject_reserved_name(self, name, "set_identity") {
+            return self;
+        }
+        self.insert((Identity::new(name), Class::new(class)))
+    }
+
+    fn add_class(&mut self, class: impl Into<Estr>) -> &mut Self {
+        let classes = match self.get::<Classes>() {
+            Some(classes) => classes.with(class),
+            None => Classes::new([class]),
+        };
+        self.insert(classes)
+    }
+
+    fn remove_class(&mut self, class: impl Into<Estr>) -> &mut Self {
+        if let Some(classes) = self.get::<Classes>() {
+            let classes = classes.without(class);
+            self.insert(classes);
+        }
+        self
+    }
+
+    fn add_alias(&mut self, alias: impl Into<Estr>) -> &mut Self {
+        let alias = normalize_name(self, alias);
+        if reject_reserved_name(self, alias, "add_alias") {
+            return self;
+        }
+        let requester = self.id();
+        let world = self.world();
+        if let Ok(owner) = world.lookup_name(alias)
+            && owner != requester
+        {
+            let error_handler = world.default_error_handler();
+            error_handler(
+                AliasTakenError {
+                    alias,
+                    owner,
+                    requester,
+                }
+                .into(),
+                ErrorContext::Command {
+                    name: "add_alias".into(),
+                },
+            );
+            return self;
+        }
+        let aliases = match self.get::<Aliases>() {
+            Some(aliases) => aliases.with(alias),
+            None => Aliases::new([alias]),
+        };
+        self.insert(aliases)
+    }
+
+    fn remove_alias(&mut self, alias: impl Into<Estr>) -> &mut Self {
+        let alias = normalize_name(self, alias);
+        if let Some(aliases) = self.get::<Aliases>() {
+            let aliases = aliases.without(alias);
+            self.insert(aliases);
+        }
+        self
+    }
 }
 
 impl<'w> RegistryCommandsExt for EntityCommands<'w> {
     fn set_name(&mut self, name: impl Into<Estr>) -> &mut Self {
-        self.insert(Identity::new(name))
+        let name = name.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_name(name);
+        })
     }
 
     fn set_class(&mut self, class: impl Into<Estr>) -> &mut Self {
         self.insert(Class::new(class))
     }
+
+    fn set_identity(&mut self, name: impl Into<Estr>, class: impl Into<Estr>) -> &mut Self {
+        let name = name.into();
+        let class = class.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_identity(name, class);
+        })
+    }
+
+    fn add_class(&mut self, class: impl Into<Estr>) -> &mut Self {
+        let class = class.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_class(class);
+        })
+    }
+
+    fn remove_class(&mut self, class: impl Into<Estr>) -> &mut Self {
+        let class = class.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_class(class);
+        })
+    }
+
+    fn add_alias(&mut self, alias: impl Into<Estr>) -> &mut Self {
+        let alias = alias.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_alias(alias);
+        })
+    }
+
+    fn remove_alias(&mut self, alias: impl Into<Estr>) -> &mut Self {
+        let alias = alias.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_alias(alias);
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Spawning named entities
+
+/// Adds helpers for spawning named entities to [`World`] and [`Commands`].
+pub trait RegistrySpawnExt {
+    /// The handle to a newly spawned entity.
+    type Spawned<'a>
+    where
+        Self: 'a;
+
+    /// Spawns an empty entity with a name, and returns it for further
+    /// configuration. Names which are already in use are handled as by
+    /// [`set_name`][RegistryCommandsExt::set_name].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// fn setup(mut commands: Commands) {
+    ///     commands.spawn_named("gandalf").set_prop("likes_elves", true);
+    ///     commands.spawn_named_class("bilbo", "hobbit");
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.run_system_cached(setup).unwrap();
+    ///
+    /// assert!(world.entity_named("gandalf").unwrap().get_prop::<bool>("likes_elves"));
+    /// assert_eq!(world.entity_named("bilbo").unwrap().get_class(), Some("hobbit".into()));
+    /// ```
+    fn spawn_named(&mut self, name: impl Into<Estr>) -> Self::Spawned<'_>;
+
+    /// Spawns an empty entity with a name and a class, and returns it for
+    /// further configuration. See [`spawn_named`][RegistrySpawnExt::spawn_named].
+    fn spawn_named_class(
+        &mut self,
+        name: impl Into<Estr>,
+        class: impl Into<Estr>,
+    ) -> Self::Spawned<'_>;
+
+    /// Spawns `count` entities of the given class, named `{base}_0` through
+    /// `{base}_{count - 1}`, and returns them in order. Names which are
+    /// already in use are handled as by
+    /// [`set_identity`][RegistryCommandsExt::set_identity], so the entity is
+    /// still spawned, but without a name.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let wave = world.spawn_named_series("enemy", 3, "zombie");
+    ///
+    /// for (i, &enemy) in wave.iter().enumerate() {
+    ///     assert_eq!(world.lookup_name(format!("enemy_{i}")).unwrap(), enemy);
+    /// }
+    /// assert_eq!(world.class_count("zombie"), 3);
+    /// ```
+    fn spawn_named_series(&mut self, base: &str, count: usize, class: &str) -> Vec<Entity>;
+}
+
+impl RegistrySpawnExt for World {
+    type Spawned<'a> = EntityWorldMut<'a>;
+
+    fn spawn_named(&mut self, name: impl Into<Estr>) -> EntityWorldMut<'_> {
+        let mut entity = self.spawn_empty();
+        entity.set_name(name);
+        entity
+    }
+
+    fn spawn_named_class(
+        &mut self,
+        name: impl Into<Estr>,
+        class: impl Into<Estr>,
+    ) -> EntityWorldMut<'_> {
+        let mut entity = self.spawn_empty();
+        entity.set_identity(name, class);
+        entity
+    }
+
+    fn spawn_named_series(&mut self, base: &str, count: usize, class: &str) -> Vec<Entity> {
+        (0..count)
+            .map(|i| {
+                self.spawn_empty()
+                    .set_identity(format!("{base}_{i}"), class)
+                    .id()
+            })
+            .collect()
+    }
+}
+
+impl<'w, 's> RegistrySpawnExt for Commands<'w, 's> {
+    type Spawned<'a>
+        = EntityCommands<'a>
+    where
+        Self: 'a;
+
+    fn spawn_named(&mut self, name: impl Into<Estr>) -> EntityCommands<'_> {
+        let mut entity = self.spawn_empty();
+        entity.set_name(name);
+        entity
+    }
+
+    fn spawn_named_class(
+        &mut self,
+        name: impl Into<Estr>,
+        class: impl Into<Estr>,
+    ) -> EntityCommands<'_> {
+        let mut entity = self.spawn_empty();
+        entity.set_identity(name, class);
+        entity
+    }
+
+    fn spawn_named_series(&mut self, base: &str, count: usize, class: &str) -> Vec<Entity> {
+        (0..count)
+            .map(|i| {
+                self.spawn_empty()
+                    .set_identity(format!("{base}_{i}"), class)
+                    .id()
+            })
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Fallible registry mutation
+
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub enum SetNameError {
+    NameTaken(#[from] NameTakenError),
+    ReservedName(#[from] ReservedNameError),
+}
+
+pub trait RegistryTryCommandsExt {
+    /// Names this entity, or returns an error if the name is already used by
+    /// another entity or uses a reserved prefix. Unlike
+    /// [`set_name`][RegistryCommandsExt::set_name], errors are returned to
+    /// the caller rather than passed to the default error handler.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// let boss = world.spawn_empty().try_set_name("boss").unwrap().id();
+    ///
+    /// let mut imposter = world.spawn_empty();
+    /// assert!(matches!(imposter.try_set_name("boss"), Err(SetNameError::NameTaken(_))));
+    /// assert_eq!(world.lookup_name("boss").unwrap(), boss);
+    /// ```
+    fn try_set_name(&mut self, name: impl Into<Estr>) -> Result<&mut Self, SetNameError>;
+}
+
+impl<'w> RegistryTryCommandsExt for EntityWorldMut<'w> {
+    fn try_set_name(&mut self, name: impl Into<Estr>) -> Result<&mut Self, SetNameError> {
+        let requester = self.id();
+        self.world_scope(|world| world.init_resource::<Registry>());
+        let registry = self.world().resource::<Registry>();
+        let name = registry.normalize(name);
+        if let Ok(owner) = registry.lookup_name(name)
+            && owner != requester
+        {
+            return Err(NameTakenError {
+                name,
+                owner,
+                requester,
+            }
+            .into());
+        }
+        if registry.reserved_prefixes().is_reserved(&name) {
+            return Err(ReservedNameError { name, requester }.into());
+        }
+        Ok(self.insert(Identity::new(name)))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Class props
+
+pub trait ClassPropsExt {
+    /// Folds a numeric property over every member of a class, without
+    /// allocating. Members without the property, or where it is not a number,
+    /// contribute `0.0`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.spawn(Class::new("party_member")).set_prop("hp", 30.0);
+    /// world.spawn(Class::new("party_member")).set_prop("hp", 80.0);
+    /// world.spawn(Class::new("enemy")).set_prop("hp", 500.0);
+    ///
+    /// let total = world.fold_prop_over_class("party_member", "hp", 0.0, |sum, hp| sum + hp);
+    /// let max = world.fold_prop_over_class("party_member", "hp", f32::MIN, f32::max);
+    /// assert_eq!(total, 110.0);
+    /// assert_eq!(max, 80.0);
+    /// ```
+    fn fold_prop_over_class<A>(
+        &self,
+        class: impl Into<Estr>,
+        name: impl Into<Estr>,
+        init: A,
+        f: impl FnMut(A, f32) -> A,
+    ) -> A;
+}
+
+impl ClassPropsExt for World {
+    fn fold_prop_over_class<A>(
+        &self,
+        class: impl Into<Estr>,
+        name: impl Into<Estr>,
+        init: A,
+        mut f: impl FnMut(A, f32) -> A,
+    ) -> A {
+        let name = name.into();
+        self.lookup_class(class).iter().fold(init, |acc, &entity| {
+            let value = self
+                .get::<Props>(entity)
+                .map_or(0.0, |props| props.get(name));
+            f(acc, value)
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Class defaults
+
+pub trait ClassDefaultsExt {
+    /// Returns a property of an entity, falling back to the [`ClassDefaults`]
+    /// of its classes and then to the default value of the type. See
+    /// [`ClassDefaults`] for how defaults are inherited.
+    ///
+    /// The primary [`Class`] is searched first, followed by each of the
+    /// entity's [`Classes`] in order of name. The first class with a default
+    /// for the property, on itself or an ancestor, decides its value.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let mut defaults = ClassDefaults::default();
+    /// defaults.insert("knight", Props::new().with("armor", 8.0));
+    /// defaults.insert("mage", Props::new().with("armor", 1.0).with("mana", 50.0));
+    /// defaults.insert("archer", Props::new().with("mana", 5.0).with("range", 20.0));
+    /// world.insert_resource(defaults);
+    ///
+    /// let spellblade = world.spawn_empty()
+    ///     .set_class("knight")
+    ///     .add_class("mage")
+    ///     .add_class("archer")
+    ///     .id();
+    ///
+    /// assert_eq!(world.resolve_prop::<f32>(spellblade, "armor"), 8.0);
+    /// assert_eq!(world.resolve_prop::<f32>(spellblade, "mana"), 5.0);
+    /// assert_eq!(world.resolve_prop::<f32>(spellblade, "range"), 20.0);
+    /// ```
+    fn resolve_prop<T>(&self, entity: Entity, name: impl Into<Estr>) -> T
+    where
+        T: From<Value> + Default + 'static;
+}
+
+impl ClassDefaultsExt for World {
+    fn resolve_prop<T>(&self, entity: Entity, name: impl Into<Estr>) -> T
+    where
+        T: From<Value> + Default + 'static,
+    {
+        let name = name.into();
+        let Ok(entity) = self.get_entity(entity) else {
+            return T::default();
+        };
+        let explicit = entity
+            .get::<Props>()
+            .and_then(|props| props.get_value(name));
+        let no_hierarchy = ClassHierarchy::default();
+        let inherited = || {
+            let defaults = self.get_resource::<ClassDefaults>()?;
+            let hierarchy = self.get_resource::<ClassHierarchy>();
+            let hierarchy = hierarchy.unwrap_or(&no_hierarchy);
+            let mut classes: Vec<Estr> = entity
+                .get::<Classes>()
+                .into_iter()
+                .flat_map(|classes| classes.iter().copied())
+                .collect();
+            classes.sort_by_key(|class| class.as_str());
+            let class = entity.get::<Class>().map(|class| **class);
+            class
+                .into_iter()
+                .chain(classes)
+                .find_map(|class| defaults.resolve(class, name, hierarchy))
+        };
+        explicit.or_else(inherited).map_or_else(T::default, T::from)
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -67,6 +583,7 @@ impl<'w> RegistryCommandsExt for EntityCommands<'w> {
 #[derive(Debug, Error)]
 #[error("{0}")]
 pub enum EntityNamedError {
+    RegistryMissing(#[from] RegistryMissingError),
     EntityNotFound(#[from] EntityNotFoundError),
     EntityNotSpawned(#[from] EntityNotSpawnedError),
 }
@@ -79,18 +596,126 @@ pub trait RegistryLookupExt {
     fn entity_named(&self, name: impl Into<Estr>) -> Result<EntityRef<'_>, EntityNamedError>;
 
     fn entity_class(&self, class: impl Into<Estr>) -> EntityClassIter<'_>;
+
+    /// Like [`entity_class`][RegistryLookupExt::entity_class], but only
+    /// yields members whose [`Props`] match a predicate. Members without a
+    /// [`Props`] component are skipped.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let orc = world.spawn((Class::new("enemy"), props! { "aggro" => true })).id();
+    /// world.spawn((Class::new("enemy"), props! { "aggro" => false }));
+    /// world.spawn(Class::new("enemy"));
+    /// world.spawn((Class::new("ally"), props! { "aggro" => true }));
+    ///
+    /// let aggro: Vec<Entity> = world
+    ///     .entity_class_where("enemy", |props| props.get("aggro"))
+    ///     .map(|entity| entity.id())
+    ///     .collect();
+    /// assert_eq!(aggro, vec![orc]);
+    /// ```
+    fn entity_class_where<F: Fn(&Props) -> bool>(
+        &self,
+        class: impl Into<Estr>,
+        f: F,
+    ) -> impl Iterator<Item = EntityRef<'_>> {
+        self.entity_class(class)
+            .filter(move |entity| entity.get::<Props>().is_some_and(&f))
+    }
+
+    /// Returns the number of entities with a class. See
+    /// [`Registry::class_count`].
+    fn class_count(&self, class: impl Into<Estr>) -> usize;
+
+    /// Returns the members of class `a` that are not in class `b`. See
+    /// [`Registry::class_difference`].
+    fn class_difference(&self, a: impl Into<Estr>, b: impl Into<Estr>) -> EntityHashSet;
+
+    /// Iterates over the entities that are members of every one of the
+    /// classes. See [`Registry::class_intersection`].
+    fn entity_class_all<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_>;
+
+    /// Iterates over the entities that are members of at least one of the
+    /// classes. See [`Registry::class_union`].
+    fn entity_class_any<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_>;
+
+    /// Like [`lookup_class`][RegistryLookupExt::lookup_class], but also
+    /// includes subclasses declared in the [`ClassHierarchy`].
+    fn lookup_class_deep(&self, class: impl Into<Estr>) -> EntityHashSet;
+
+    /// Like [`entity_class`][RegistryLookupExt::entity_class], but also
+    /// includes subclasses declared in the [`ClassHierarchy`].
+    fn entity_class_deep(&self, class: impl Into<Estr>) -> EntityClassIter<'_>;
+
+    /// Returns every named entity whose name matches a glob pattern. See
+    /// [`Registry::lookup_pattern`].
+    fn lookup_pattern(&self, pattern: &str) -> Vec<Entity>;
+
+    /// Creates a borrowing iterator over all registered names. See
+    /// [`Registry::names`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let sam = world.spawn_empty().set_identity("sam", "hobbit").id();
+    ///
+    /// assert_eq!(world.registered_names().collect::<Vec<_>>(), vec![("sam".into(), sam)]);
+    /// assert_eq!(world.registered_classes().collect::<Vec<_>>(), vec![&Estr::from("hobbit")]);
+    /// ```
+    fn registered_names(&self) -> impl Iterator<Item = (Estr, Entity)> + '_;
+
+    /// Creates a borrowing iterator over all classes with at least one entity.
+    /// See [`Registry::classes`].
+    fn registered_classes(&self) -> impl Iterator<Item = &Estr>;
 }
 
+/// Iterates over the members of a class.
+///
+/// For [`entity_class`][RegistryLookupExt::entity_class], this borrows the
+/// registry's index directly, so creating the iterator does not allocate.
 pub struct EntityClassIter<'w> {
-    entities: bevy_ecs::entity::hash_set::IntoIter,
+    entities: ClassEntities<'w>,
     world: &'w World,
 }
 
+/// The entities visited by an [`EntityClassIter`], either borrowed from the
+/// registry or computed for the iterator.
+enum ClassEntities<'w> {
+    Borrowed(hash_set::Iter<'w>),
+    Owned(hash_set::IntoIter),
+}
+
+impl<'w> Iterator for ClassEntities<'w> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        match self {
+            ClassEntities::Borrowed(entities) => entities.next().copied(),
+            ClassEntities::Owned(entities) => entities.next(),
+        }
+    }
+}
+
 impl<'w> Iterator for EntityClassIter<'w> {
     type Item = EntityRef<'w>;
 
+    /// Returns the next member of the class, skipping entities which are no
+    /// longer spawned.
     fn next(&mut self) -> Option<Self::Item> {
-        let entity = self.entities.next()?;
+        let entity = self
+            .entities
+            .find(|&entity| self.world.entities().contains_spawned(entity))?;
         Some(self.world.entity(entity))
     }
 }
@@ -113,17 +738,95 @@ impl RegistryLookupExt for World {
     }
 
     fn entity_named(&self, name: impl Into<Estr>) -> Result<EntityRef<'_>, EntityNamedError> {
-        let entity = self.lookup_name(name)?;
+        let registry = self
+            .get_resource::<Registry>()
+            .ok_or(RegistryMissingError)?;
+        let entity = registry.lookup_name(name)?;
         let entity_ref = self.get_entity(entity)?;
         Ok(entity_ref)
     }
 
     fn entity_class(&self, class: impl Into<Estr>) -> EntityClassIter<'_> {
         EntityClassIter {
-            entities: self.lookup_class(class).clone().into_iter(),
+            entities: ClassEntities::Borrowed(self.lookup_class(class).iter()),
             world: self,
         }
     }
+
+    fn class_count(&self, class: impl Into<Estr>) -> usize {
+        self.lookup_class(class).len()
+    }
+
+    fn class_difference(&self, a: impl Into<Estr>, b: impl Into<Estr>) -> EntityHashSet {
+        match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_difference(a, b),
+            None => EntityHashSet::new(),
+        }
+    }
+
+    fn entity_class_all<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_> {
+        let entities = match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_intersection(classes),
+            None => EntityHashSet::new(),
+        };
+        EntityClassIter {
+            entities: ClassEntities::Owned(entities.into_iter()),
+            world: self,
+        }
+    }
+
+    fn entity_class_any<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_> {
+        let entities = match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_union(classes),
+            None => EntityHashSet::new(),
+        };
+        EntityClassIter {
+            entities: ClassEntities::Owned(entities.into_iter()),
+            world: self,
+        }
+    }
+
+    fn lookup_class_deep(&self, class: impl Into<Estr>) -> EntityHashSet {
+        match (
+            self.get_resource::<Registry>(),
+            self.get_resource::<ClassHierarchy>(),
+        ) {
+            (Some(registry), Some(hierarchy)) => registry.lookup_class_deep(class, hierarchy),
+            _ => self.lookup_class(class).clone(),
+        }
+    }
+
+    fn entity_class_deep(&self, class: impl Into<Estr>) -> EntityClassIter<'_> {
+        EntityClassIter {
+            entities: ClassEntities::Owned(self.lookup_class_deep(class).into_iter()),
+            world: self,
+        }
+    }
+
+    fn lookup_pattern(&self, pattern: &str) -> Vec<Entity> {
+        match self.get_resource::<Registry>() {
+            Some(registry) => registry.lookup_pattern(pattern),
+            None => Vec::new(),
+        }
+    }
+
+    fn registered_names(&self) -> impl Iterator<Item = (Estr, Entity)> + '_ {
+        self.get_resource::<Registry>()
+            .into_iter()
+            .flat_map(Registry::names)
+    }
+
+    fn registered_classes(&self) -> impl Iterator<Item = &Estr> {
+        self.get_resource::<Registry>()
+            .into_iter()
+            .flat_map(Registry::classes)
+    }
 }
 
 impl<'w> RegistryLookupExt for DeferredWorld<'w> {
@@ -144,17 +847,95 @@ impl<'w> RegistryLookupExt for DeferredWorld<'w> {
     }
 
     fn entity_named(&self, name: impl Into<Estr>) -> Result<EntityRef<'_>, EntityNamedError> {
-        let entity = self.lookup_name(name)?;
+        let registry = self
+            .get_resource::<Registry>()
+            .ok_or(RegistryMissingError)?;
+        let entity = registry.lookup_name(name)?;
         let entity_ref = self.get_entity(entity)?;
         Ok(entity_ref)
     }
 
     fn entity_class(&self, class: impl Into<Estr>) -> EntityClassIter<'_> {
         EntityClassIter {
-            entities: self.lookup_class(class).clone().into_iter(),
+            entities: ClassEntities::Borrowed(self.lookup_class(class).iter()),
             world: self,
         }
     }
+
+    fn class_count(&self, class: impl Into<Estr>) -> usize {
+        self.lookup_class(class).len()
+    }
+
+    fn class_difference(&self, a: impl Into<Estr>, b: impl Into<Estr>) -> EntityHashSet {
+        match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_difference(a, b),
+            None => EntityHashSet::new(),
+        }
+    }
+
+    fn entity_class_all<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_> {
+        let entities = match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_intersection(classes),
+            None => EntityHashSet::new(),
+        };
+        EntityClassIter {
+            entities: ClassEntities::Owned(entities.into_iter()),
+            world: self,
+        }
+    }
+
+    fn entity_class_any<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityClassIter<'_> {
+        let entities = match self.get_resource::<Registry>() {
+            Some(registry) => registry.class_union(classes),
+            None => EntityHashSet::new(),
+        };
+        EntityClassIter {
+            entities: ClassEntities::Owned(entities.into_iter()),
+            world: self,
+        }
+    }
+
+    fn lookup_class_deep(&self, class: impl Into<Estr>) -> EntityHashSet {
+        match (
+            self.get_resource::<Registry>(),
+            self.get_resource::<ClassHierarchy>(),
+        ) {
+            (Some(registry), Some(hierarchy)) => registry.lookup_class_deep(class, hierarchy),
+            _ => self.lookup_class(class).clone(),
+        }
+    }
+
+    fn entity_class_deep(&self, class: impl Into<Estr>) -> EntityClassIter<'_> {
+        EntityClassIter {
+            entities: ClassEntities::Owned(self.lookup_class_deep(class).into_iter()),
+            world: self,
+        }
+    }
+
+    fn lookup_pattern(&self, pattern: &str) -> Vec<Entity> {
+        match self.get_resource::<Registry>() {
+            Some(registry) => registry.lookup_pattern(pattern),
+            None => Vec::new(),
+        }
+    }
+
+    fn registered_names(&self) -> impl Iterator<Item = (Estr, Entity)> + '_ {
+        self.get_resource::<Registry>()
+            .into_iter()
+            .flat_map(Registry::names)
+    }
+
+    fn registered_classes(&self) -> impl Iterator<Item = &Estr> {
+        self.get_resource::<Registry>()
+            .into_iter()
+            .flat_map(Registry::classes)
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -163,6 +944,7 @@ impl<'w> RegistryLookupExt for DeferredWorld<'w> {
 #[derive(Debug, Error)]
 #[error("{0}")]
 pub enum EntityNamedMutError {
+    RegistryMissing(#[from] RegistryMissingError),
     EntityNotFound(#[from] EntityNotFoundError),
     EntityMutableFetchError(#[from] EntityMutableFetchError),
 }
@@ -174,22 +956,150 @@ pub trait RegistryLookupMutExt {
     ) -> Result<EntityWorldMut<'_>, EntityNamedMutError>;
 
     fn entity_mut_class(&mut self, class: impl Into<Estr>) -> EntityClassMutIter<'_>;
+
+    /// Calls a function on each member of a class. Members which no longer
+    /// exist are skipped.
+    ///
+    /// Unlike [`entity_mut_class`][Self::entity_mut_class], this borrows the
+    /// registry's index in place rather than copying it, so it does not
+    /// allocate. In exchange, each member is passed as an [`EntityMut`], which
+    /// can change components but can not add or remove them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.spawn((Class::new("orc"), Props::new()));
+    /// world.spawn((Class::new("orc"), Props::new()));
+    ///
+    /// world.for_each_in_class("orc", |mut orc| {
+    ///     orc.get_mut::<Props>().unwrap().set("angry", true);
+    /// });
+    ///
+    /// assert!(world.entity_class("orc").all(|orc| orc.get_prop::<bool>("angry")));
+    /// ```
+    fn for_each_in_class(&mut self, class: impl Into<Estr>, f: impl FnMut(EntityMut<'_>));
+
+    /// Returns the entity with a name, spawning and naming a new entity if
+    /// there isn't one. The registry is updated before this returns, so the
+    /// new entity can be looked up by name straight away.
+    ///
+    /// If the name uses a reserved prefix, a new entity is spawned each time,
+    /// without a name, and an error is passed to the default error handler as
+    /// by [`set_name`][RegistryCommandsExt::set_name].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    ///
+    /// let player = world.get_or_spawn_named("player").set_prop("lives", 3.0).id();
+    /// assert_eq!(world.lookup_name("player").unwrap(), player);
+    ///
+    /// let again = world.get_or_spawn_named("player");
+    /// assert_eq!(again.id(), player);
+    /// assert_eq!(again.get_prop::<f32>("lives"), 3.0);
+    /// ```
+    fn get_or_spawn_named(&mut self, name: impl Into<Estr>) -> EntityWorldMut<'_>;
+
+    /// Returns mutable references to several named entities at once. Fails
+    /// with [`EntityMutableFetchError::AliasedMutability`] if two of the names
+    /// refer to the same entity, for example through an alias.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.spawn_named("player").set_prop("hp", 10.0);
+    /// world.spawn_named("boss").set_prop("hp", 50.0).add_alias("dragon");
+    ///
+    /// let [mut player, mut boss] = world.entities_mut_named(["player", "boss"]).unwrap();
+    /// let mut player = player.get_mut::<Props>().unwrap();
+    /// let mut boss = boss.get_mut::<Props>().unwrap();
+    /// boss.decrement("hp", player.get("hp"));
+    /// player.set("hp", 0.0);
+    /// assert_eq!(world.entity_named("boss").unwrap().get_prop::<f32>("hp"), 40.0);
+    ///
+    /// assert!(world.entities_mut_named(["boss", "dragon"]).is_err());
+    /// ```
+    fn entities_mut_named<S: Into<Estr>, const N: usize>(
+        &mut self,
+        names: [S; N],
+    ) -> Result<[EntityMut<'_>; N], EntityNamedMutError>;
 }
 
+/// Visits each member of a class mutably, one at a time.
+///
+/// Each [`EntityWorldMut`] can access the whole world, so only one may be live
+/// at once. This is a lending iterator rather than an [`Iterator`]: each entity
+/// returned by [`next`][EntityClassMutIter::next] borrows the iterator, and must
+/// be dropped before the next one is requested.
+///
+/// Because the visited entities may change the registry, the members of the
+/// class are copied when the iterator is created. Prefer
+/// [`entity_class`][RegistryLookupExt::entity_class] when only reading, and
+/// [`for_each_in_class`][RegistryLookupMutExt::for_each_in_class] when only
+/// changing existing components.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// world.spawn(Class::new("orc"));
+/// world.spawn(Class::new("orc"));
+///
+/// let mut orcs = world.entity_mut_class("orc");
+/// while let Some(mut orc) = orcs.next() {
+///     orc.set_prop("angry", true);
+/// }
+///
+/// assert!(world.entity_class("orc").all(|orc| orc.get_prop::<bool>("angry")));
+/// ```
+///
+/// Holding two entities at once is rejected by the borrow checker:
+///
+/// ```compile_fail
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// let mut orcs = world.entity_mut_class("orc");
+/// let first = orcs.next();
+/// let second = orcs.next();
+/// drop(first);
+/// ```
 pub struct EntityClassMutIter<'w> {
     entities: bevy_ecs::entity::hash_set::IntoIter,
-    world_cell: UnsafeWorldCell<'w>,
+    world: &'w mut World,
 }
 
-impl<'w> Iterator for EntityClassMutIter<'w> {
-    type Item = EntityWorldMut<'w>;
+impl<'w> EntityClassMutIter<'w> {
+    /// Returns the next member of the class. Entities which have been
+    /// despawned since the iterator was created are skipped.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<EntityWorldMut<'_>> {
+        let entity = self
+            .entities
+            .find(|&entity| self.world.entities().contains_spawned(entity))?;
+        Some(self.world.entity_mut(entity))
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let entity = self.entities.next()?;
-        // SAFETY: TODO
-        let entity_mut = unsafe { entity.fetch_mut(self.world_cell).unwrap() };
-        Some(entity_mut)
+/// Looks up several names in the registry, for the `entities_mut_named`
+/// methods.
+fn lookup_names<S: Into<Estr>, const N: usize>(
+    registry: Option<&Registry>,
+    names: [S; N],
+) -> Result<[Entity; N], EntityNamedMutError> {
+    let registry = registry.ok_or(RegistryMissingError)?;
+    let mut entities = [Entity::PLACEHOLDER; N];
+    for (entity, name) in entities.iter_mut().zip(names) {
+        *entity = registry.lookup_name(name)?;
     }
+    Ok(entities)
 }
 
 impl RegistryLookupMutExt for World {
@@ -197,7 +1107,10 @@ impl RegistryLookupMutExt for World {
         &mut self,
         name: impl Into<Estr>,
     ) -> Result<EntityWorldMut<'_>, EntityNamedMutError> {
-        let entity = self.lookup_name(name)?;
+        let registry = self
+            .get_resource::<Registry>()
+            .ok_or(RegistryMissingError)?;
+        let entity = registry.lookup_name(name)?;
         let entity_mut = self.get_entity_mut(entity)?;
         Ok(entity_mut)
     }
@@ -205,9 +1118,32 @@ impl RegistryLookupMutExt for World {
     fn entity_mut_class(&mut self, class: impl Into<Estr>) -> EntityClassMutIter<'_> {
         EntityClassMutIter {
             entities: self.lookup_class(class).clone().into_iter(),
-            world_cell: self.as_unsafe_world_cell(),
+            world: self,
+        }
+    }
+
+    fn for_each_in_class(&mut self, class: impl Into<Estr>, f: impl FnMut(EntityMut<'_>)) {
+        DeferredWorld::from(self)
+            .entity_mut_class(class)
+            .for_each(f);
+    }
+
+    fn get_or_spawn_named(&mut self, name: impl Into<Estr>) -> EntityWorldMut<'_> {
+        let name = name.into();
+        match self.lookup_name(name) {
+            Ok(entity) if self.entities().contains_spawned(entity) => self.entity_mut(entity),
+            _ => self.spawn_named(name),
         }
     }
+
+    fn entities_mut_named<S: Into<Estr>, const N: usize>(
+        &mut self,
+        names: [S; N],
+    ) -> Result<[EntityMut<'_>; N], EntityNamedMutError> {
+        let entities = lookup_names(self.get_resource::<Registry>(), names)?;
+        let entities_mut = self.get_entity_mut(entities)?;
+        Ok(entities_mut)
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -220,6 +1156,11 @@ pub trait RegistryLookupDeferredExt {
     ) -> Result<EntityMut<'_>, EntityNamedMutError>;
 
     fn entity_mut_class(&mut self, class: impl Into<Estr>) -> EntityClassDeferredIter<'_>;
+
+    fn entities_mut_named<S: Into<Estr>, const N: usize>(
+        &mut self,
+        names: [S; N],
+    ) -> Result<[EntityMut<'_>; N], EntityNamedMutError>;
 }
 
 impl<'w> RegistryLookupDeferredExt for DeferredWorld<'w> {
@@ -227,21 +1168,71 @@ impl<'w> RegistryLookupDeferredExt for DeferredWorld<'w> {
         &mut self,
         name: impl Into<Estr>,
     ) -> Result<EntityMut<'_>, EntityNamedMutError> {
-        let entity = self.lookup_name(name)?;
+        let registry = self
+            .get_resource::<Registry>()
+            .ok_or(RegistryMissingError)?;
+        let entity = registry.lookup_name(name)?;
         let entity_mut = self.get_entity_mut(entity)?;
         Ok(entity_mut)
     }
 
     fn entity_mut_class(&mut self, class: impl Into<Estr>) -> EntityClassDeferredIter<'_> {
+        // Only non-structural changes are made through this cell: the
+        // iterator hands out `EntityMut`s, which can not add or remove
+        // components or despawn entities.
+        let world_cell = self.as_unsafe_world_cell();
+        // SAFETY: The cell was created from `&mut self`, so it has access to
+        // every resource, and nothing else can access the world while the
+        // iterator exists. The registry is only read, and the `EntityMut`s
+        // returned by the iterator can not access resources, so there are no
+        // mutable references to it.
+        let registry = unsafe { world_cell.get_resource::<Registry>() };
+        let entities = match registry {
+            Some(registry) => registry.lookup_class(class),
+            None => &EMPTY_SET,
+        };
         EntityClassDeferredIter {
-            entities: self.lookup_class(class).clone().into_iter(),
-            world_cell: self.as_unsafe_world_cell_readonly(),
+            entities: entities.iter(),
+            world_cell,
         }
     }
+
+    fn entities_mut_named<S: Into<Estr>, const N: usize>(
+        &mut self,
+        names: [S; N],
+    ) -> Result<[EntityMut<'_>; N], EntityNamedMutError> {
+        let entities = lookup_names(self.get_resource::<Registry>(), names)?;
+        let entities_mut = self.get_entity_mut(entities)?;
+        Ok(entities_mut)
+    }
 }
 
+/// Iterates mutably over the members of a class in a [`DeferredWorld`].
+///
+/// This borrows the registry's index directly, so creating the iterator does
+/// not allocate. Members which no longer exist are skipped.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::DeferredWorld;
+/// # use bevy_mod_props::prelude::*;
+/// #[derive(Component)]
+/// struct Alarm;
+///
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// world.add_observer(|_: On<Add, Alarm>, mut world: DeferredWorld| {
+///     for mut guard in world.entity_mut_class("guard") {
+///         guard.get_mut::<Props>().unwrap().set("alert", true);
+///     }
+/// });
+/// let guard = world.spawn((Class::new("guard"), Props::new())).id();
+/// world.spawn(Alarm);
+///
+/// assert!(world.entity(guard).get_prop::<bool>("alert"));
+/// ```
 pub struct EntityClassDeferredIter<'w> {
-    entities: bevy_ecs::entity::hash_set::IntoIter,
+    entities: hash_set::Iter<'w>,
     world_cell: UnsafeWorldCell<'w>,
 }
 
@@ -249,9 +1240,15 @@ impl<'w> Iterator for EntityClassDeferredIter<'w> {
     type Item = EntityMut<'w>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let entity = self.entities.next()?;
-        // SAFETY: TODO
-        let entity_mut = unsafe { entity.fetch_deferred_mut(self.world_cell).unwrap() };
-        Some(entity_mut)
+        loop {
+            let entity = *self.entities.next()?;
+            // SAFETY: The cell was created from `&mut DeferredWorld`, so it has
+            // mutable access to every entity. Each entity appears only once in
+            // the class index, so no other references to it have been handed
+            // out by this iterator.
+            if let Ok(entity_mut) = unsafe { entity.fetch_deferred_mut(self.world_cell) } {
+                return Some(entity_mut);
+            }
+        }
     }
 }
@@ -1,7 +1,8 @@
 //! Adds a global registry of unique entity names and classes.
 //!
 //! Names are unique: only one entity may use a given name at a time. Classes
-//! are non-unique, but each entity may only have one class. Both names and
+//! are non-unique. Each entity may have one primary [`Class`], and any number
+//! of additional classes through the [`Classes`] component. Both names and
 //! classes are global properties, attached to the world.
 //!
 //! ```rust
@@ -25,8 +26,9 @@
 //! world.entity_mut_named("legolas")?
 //!     .set_prop("equiped", "elven_knives");
 //!
-//! // or iterate through all members of class
-//! for mut party_member in world.entity_mut_class("party_member") {
+//! // or visit each member of class in turn
+//! let mut party = world.entity_mut_class("party_member");
+//! while let Some(mut party_member) = party.next() {
 //!     party_member
 //!         .set_prop("has_seen_ringwraith", true);
 //! }
@@ -35,18 +37,31 @@
 //! ```
 //!
 
+mod events;
 mod ext;
+mod param;
 use std::{ops::Deref, sync::LazyLock};
 
+#[cfg(feature = "reflect")]
+use bevy_ecs::reflect::ReflectComponent;
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityHashMap, EntityHashSet},
-    lifecycle::HookContext,
+    lifecycle::{HookContext, Insert},
+    observer::On,
     resource::Resource,
+    system::Commands,
     world::{DeferredWorld, World},
 };
-use estr::{Estr, EstrMap};
+#[cfg(feature = "reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use estr::{Estr, EstrMap, EstrSet};
+
+use crate::props::{Props, Value};
+pub(crate) use events::send_change_messages;
+pub use events::*;
 pub use ext::*;
+pub use param::*;
 use thiserror::Error;
 
 // -----------------------------------------------------------------------------
@@ -62,12 +77,57 @@ pub struct NameTakenError {
     requester: Entity,
 }
 
+#[derive(Debug, Error)]
+#[error("error adding alias: alias {alias} requested by {requester} already in use by {owner}")]
+pub struct AliasTakenError {
+    alias: Estr,
+    owner: Entity,
+    requester: Entity,
+}
+
 #[derive(Debug, Error)]
 #[error("no entity found with name '{name}'")]
 pub struct EntityNotFoundError {
     name: Estr,
 }
 
+/// Returned by lookups that need the [`Registry`] resource when it has not
+/// been added to the world, usually because [`PropsPlugin`] is missing.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// assert!(matches!(
+///     world.entity_named("frodo"),
+///     Err(EntityNamedError::RegistryMissing(_))
+/// ));
+///
+/// world.init_resource::<Registry>();
+/// assert!(matches!(
+///     world.entity_named("frodo"),
+///     Err(EntityNamedError::EntityNotFound(_))
+/// ));
+/// ```
+///
+/// [`PropsPlugin`]: crate::plugin::PropsPlugin
+#[derive(Debug, Error)]
+#[error(
+    "the `Registry` resource is missing; add `PropsPlugin` or call `init_resource::<Registry>()`"
+)]
+pub struct RegistryMissingError;
+
+#[derive(Debug, Error)]
+#[error("error setting name: name {name} requested by {requester} uses a reserved prefix")]
+pub struct ReservedNameError {
+    name: Estr,
+    requester: Entity,
+}
+
+#[derive(Debug, Error)]
+#[error("the name normalizer can't be changed after names have been registered")]
+pub struct NormalizerLockedError;
+
 // -----------------------------------------------------------------------------
 // The Identity Component
 
@@ -75,8 +135,49 @@ pub struct EntityNotFoundError {
 ///
 /// There can only be one entity with a given identiy string. Adding an identity
 /// that is already in use is not allowed; the component will be automatically
-/// removed and an error will be logged.
+/// removed and a [`NameTakenError`] is passed to the default error handler. In
+/// other words, the entity that claimed the name first keeps it. Use
+/// [`try_set_name`][RegistryTryCommandsExt::try_set_name] to check for
+/// collisions up front.
+///
+/// The component is immutable, so changing a name always replaces the
+/// component. The registry hooks remove the old name from the index when the
+/// component is replaced, removed, or despawned, and add the new name when it
+/// is inserted. Despawned entities therefore can't be looked up by name.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let boromir = world.spawn_empty().set_identity("boromir", "gondorian").id();
+/// world.despawn(boromir);
+///
+/// assert!(world.lookup_name("boromir").is_err());
+/// assert!(world.entity_named("boromir").is_err());
+/// assert!(world.lookup_class("gondorian").is_empty());
+/// ```
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::error::{ignore, DefaultErrorHandler};
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// # world.insert_resource(DefaultErrorHandler(ignore));
+/// let first = world.spawn_empty().set_name("boss").id();
+/// let second = world.spawn_empty().set_name("boss").id();
+/// world.flush();
+///
+/// assert_eq!(world.lookup_name("boss").unwrap(), first);
+/// assert!(world.entity(second).get::<Identity>().is_none());
+/// ```
 #[derive(Component, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Debug, Clone, PartialEq, Hash)
+)]
 #[component(immutable)]
 #[component(on_insert = Identity::on_insert)]
 #[component(on_replace = Identity::on_replace)]
@@ -91,9 +192,40 @@ impl Identity {
         let Identity(name) = *world.entity(context.entity).get::<Identity>().unwrap();
         if let Some(mut registry) = world.get_resource_mut::<Registry>() {
             // The registry exists in the world
-            if let Some(&owner) = registry.named_entities.get(&name) {
-                // We explicetly allow re-inserting the same name on an entity
-                if owner != context.entity {
+            let name = registry.normalize(name);
+            // We explicetly allow re-inserting the same name on an entity, or
+            // using one of the entity's own aliases as its name
+            if let Some(&owner) = registry.named_entities.get(&name)
+                && owner != context.entity
+            {
+                // The name is already in use by a different entity, remove the component and return an error
+                world.commands().entity(context.entity).remove::<Identity>();
+                let error_handler = world.default_error_handler();
+                error_handler(
+                    NameTakenError {
+                        name,
+                        owner,
+                        requester: context.entity,
+                    }
+                    .into(),
+                    bevy_ecs::error::ErrorContext::Observer {
+                        name: "Identity::on_insert".into(),
+                        last_run: world.last_change_tick(),
+                    },
+                );
+            } else {
+                // The name is not in use by another entity, add it
+                registry.add_name_source(context.entity, name);
+                registry.reigrations.entry(context.entity).or_default().name = Some(name);
+            }
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                // The registry exists in the world
+                let name = registry.normalize(name);
+                if let Some(&owner) = registry.named_entities.get(&name)
+                    && owner != context.entity
+                {
                     // The name is already in use by a different entity, remove the component and return an error
                     world.commands().entity(context.entity).remove::<Identity>();
                     let error_handler = world.default_error_handler();
@@ -109,38 +241,9 @@ impl Identity {
                             last_run: world.last_change_tick(),
                         },
                     );
-                }
-            } else {
-                // The name is not already in use, add it
-                registry.named_entities.insert(name, context.entity);
-                registry.reigrations.entry(context.entity).or_default().name = Some(name);
-            }
-        } else {
-            world.commands().queue(move |world: &mut World| {
-                let mut registry = world.get_resource_or_init::<Registry>();
-                // The registry exists in the world
-                if let Some(&owner) = registry.named_entities.get(&name) {
-                    // We explicetly allow re-inserting the same name on an entity
-                    if owner != context.entity {
-                        // The name is already in use by a different entity, remove the component and return an error
-                        world.commands().entity(context.entity).remove::<Identity>();
-                        let error_handler = world.default_error_handler();
-                        error_handler(
-                            NameTakenError {
-                                name,
-                                owner,
-                                requester: context.entity,
-                            }
-                            .into(),
-                            bevy_ecs::error::ErrorContext::Observer {
-                                name: "Identity::on_insert".into(),
-                                last_run: world.last_change_tick(),
-                            },
-                        );
-                    }
                 } else {
-                    // The name is not already in use, add it
-                    registry.named_entities.insert(name, context.entity);
+                    // The name is not in use by another entity, add it
+                    registry.add_name_source(context.entity, name);
                     registry.reigrations.entry(context.entity).or_default().name = Some(name);
                 }
             })
@@ -149,22 +252,17 @@ impl Identity {
 
     fn on_replace(mut world: DeferredWorld, context: HookContext) {
         let Identity(name) = *world.entity(context.entity).get::<Identity>().unwrap();
+        // The name stays in the index while the entity also uses it as an alias
         if let Some(mut registry) = world.get_resource_mut::<Registry>() {
-            if let Some(registration) = registry.reigrations.get_mut(&context.entity) {
-                registration.name = None;
-            }
-            if registry.named_entities.get(&name) == Some(&context.entity) {
-                registry.named_entities.remove(&name);
-            }
+            let name = registry.normalize(name);
+            registry.unregister_name(context.entity);
+            registry.remove_name_source(context.entity, name);
         } else {
             world.commands().queue(move |world: &mut World| {
                 let mut registry = world.get_resource_or_init::<Registry>();
-                if let Some(registration) = registry.reigrations.get_mut(&context.entity) {
-                    registration.name = None;
-                }
-                if registry.named_entities.get(&name) == Some(&context.entity) {
-                    registry.named_entities.remove(&name);
-                }
+                let name = registry.normalize(name);
+                registry.unregister_name(context.entity);
+                registry.remove_name_source(context.entity, name);
             });
         }
     }
@@ -184,8 +282,14 @@ impl Deref for Identity {
 /// Identifies the class to which this entity belongs.
 ///
 /// A class is simply a named set of entities. Each entity may have exactly
-/// one class. Each entity may only belong to one class.
+/// one class. Each entity may only belong to one class; use [`Classes`] for
+/// entities that belong to several.
 #[derive(Component, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Debug, Clone, PartialEq, Hash)
+)]
 #[component(immutable)]
 #[component(on_insert = Class::on_insert)]
 #[component(on_replace = Class::on_replace)]
@@ -204,8 +308,7 @@ impl Class {
                 .entry(context.entity)
                 .or_default()
                 .class = Some(class);
-            let class = registry.entity_classes.entry(class).or_default();
-            class.insert(context.entity);
+            registry.add_class_source(context.entity, class);
         } else {
             world.commands().queue(move |world: &mut World| {
                 let mut registry = world.get_resource_or_init::<Registry>();
@@ -214,24 +317,22 @@ impl Class {
                     .entry(context.entity)
                     .or_default()
                     .class = Some(class);
-                let class = registry.entity_classes.entry(class).or_default();
-                class.insert(context.entity);
+                registry.add_class_source(context.entity, class);
             });
         }
     }
 
     fn on_replace(mut world: DeferredWorld, context: HookContext) {
         let Class(class) = *world.entity(context.entity).get::<Class>().unwrap();
+        // The entity stays in the class index while it also has the class in `Classes`
         if let Some(mut registry) = world.get_resource_mut::<Registry>() {
-            registry.reigrations.get_mut(&context.entity).unwrap().class = None;
-            let class = registry.entity_classes.entry(class).or_default();
-            class.remove(&context.entity);
+            registry.unregister_class(context.entity);
+            registry.remove_class_source(context.entity, class);
         } else {
             world.commands().queue(move |world: &mut World| {
                 let mut registry = world.get_resource_or_init::<Registry>();
-                registry.reigrations.get_mut(&context.entity).unwrap().class = None;
-                let class = registry.entity_classes.entry(class).or_default();
-                class.remove(&context.entity);
+                registry.unregister_class(context.entity);
+                registry.remove_class_source(context.entity, class);
             });
         }
     }
@@ -245,6 +346,307 @@ impl Deref for Class {
     }
 }
 
+// -----------------------------------------------------------------------------
+// The Classes Component
+
+/// Identifies additional classes to which this entity belongs.
+///
+/// Where [`Class`] holds a single class, this holds a set of them, so an
+/// entity can be both an `"enemy"` and `"flying"`. The entity is found under
+/// each of these classes by [`lookup_class`][Registry::lookup_class], in the
+/// same way as for its [`Class`]. It is usually easiest to manage this
+/// component with [`add_class`][RegistryCommandsExt::add_class] and
+/// [`remove_class`][RegistryCommandsExt::remove_class].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let bat = world.spawn_empty()
+///     .set_class("enemy")
+///     .add_class("flying")
+///     .id();
+///
+/// assert!(world.lookup_class("enemy").contains(&bat));
+/// assert!(world.lookup_class("flying").contains(&bat));
+///
+/// world.entity_mut(bat).remove_class("flying");
+/// assert!(world.lookup_class("flying").is_empty());
+/// assert!(world.entity(bat).has_class("enemy"));
+/// ```
+///
+/// An entity can have the same class as its [`Class`] and in its `Classes`.
+/// It stays in the class until both are gone.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let orc = world.spawn(Class::new("enemy")).add_class("enemy").id();
+/// let goblin = world.spawn(Class::new("enemy")).id();
+///
+/// world.entity_mut(orc).remove_class("enemy");
+/// assert!(world.lookup_class("enemy").contains(&orc));
+///
+/// world.entity_mut(orc).add_class("enemy");
+/// world.despawn(orc);
+/// assert!(!world.lookup_class("enemy").contains(&orc));
+/// let enemies: Vec<Entity> = world.entity_class("enemy").map(|enemy| enemy.id()).collect();
+/// assert_eq!(enemies, vec![goblin]);
+/// ```
+#[derive(Component, Clone, Default, Eq, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Debug, Default, Clone, PartialEq)
+)]
+#[component(immutable)]
+#[component(on_insert = Classes::on_insert)]
+#[component(on_replace = Classes::on_replace)]
+#[component(on_remove = Classes::on_remove)]
+pub struct Classes(EstrSet);
+
+impl Classes {
+    pub fn new<S: Into<Estr>>(classes: impl IntoIterator<Item = S>) -> Classes {
+        Classes(classes.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns a copy of these classes with one more class added.
+    pub fn with(&self, class: impl Into<Estr>) -> Classes {
+        let mut classes = self.clone();
+        classes.0.insert(class.into());
+        classes
+    }
+
+    /// Returns a copy of these classes with a class removed.
+    pub fn without(&self, class: impl Into<Estr>) -> Classes {
+        let mut classes = self.clone();
+        classes.0.remove(&class.into());
+        classes
+    }
+
+    // Replacing the classes only updates the index for the classes that were
+    // added or removed. The old classes are held by the registry between the
+    // `on_replace` hook and the `on_insert` or `on_remove` hook that follows.
+
+    fn on_insert(mut world: DeferredWorld, context: HookContext) {
+        let classes = world
+            .entity(context.entity)
+            .get::<Classes>()
+            .unwrap()
+            .clone();
+        if let Some(mut registry) = world.get_resource_mut::<Registry>() {
+            registry.update_classes(context.entity, &classes);
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                registry.update_classes(context.entity, &classes);
+            });
+        }
+    }
+
+    fn on_replace(mut world: DeferredWorld, context: HookContext) {
+        let classes = world
+            .entity(context.entity)
+            .get::<Classes>()
+            .unwrap()
+            .clone();
+        if let Some(mut registry) = world.get_resource_mut::<Registry>() {
+            registry.replaced_classes.insert(context.entity, classes);
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                registry.replaced_classes.insert(context.entity, classes);
+            });
+        }
+    }
+
+    fn on_remove(mut world: DeferredWorld, context: HookContext) {
+        if let Some(mut registry) = world.get_resource_mut::<Registry>() {
+            registry.update_classes(context.entity, &Classes::default());
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                registry.update_classes(context.entity, &Classes::default());
+            });
+        }
+    }
+}
+
+impl Deref for Classes {
+    type Target = EstrSet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: Into<Estr>> FromIterator<S> for Classes {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Classes::new(iter)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// The Aliases Component
+
+/// Gives an entity additional names alongside its [`Identity`].
+///
+/// Aliases share the registry's name index with identities, so
+/// [`lookup_name`][Registry::lookup_name] resolves an alias to its entity in
+/// the same way as a name. Names and aliases must be unique together: an alias
+/// that is already used by another entity is not registered, and an
+/// [`AliasTakenError`] is passed to the default error handler. It is usually
+/// easiest to manage this component with
+/// [`add_alias`][RegistryCommandsExt::add_alias] and
+/// [`remove_alias`][RegistryCommandsExt::remove_alias], which check for
+/// collisions before changing the component.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::error::{ignore, DefaultErrorHandler};
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// # world.insert_resource(DefaultErrorHandler(ignore));
+/// let player = world.spawn_empty()
+///     .set_name("player")
+///     .add_alias("hero")
+///     .add_alias("you")
+///     .id();
+/// let frodo = world.spawn_empty().set_name("frodo").id();
+///
+/// assert_eq!(world.lookup_name("hero").unwrap(), player);
+/// assert_eq!(world.entity(player).get_aliases(), ["hero", "you"]);
+///
+/// // aliases can't collide with other entities' names
+/// world.entity_mut(player).add_alias("frodo");
+/// assert_eq!(world.lookup_name("frodo").unwrap(), frodo);
+///
+/// world.entity_mut(player).remove_alias("you");
+/// assert!(world.lookup_name("you").is_err());
+/// assert_eq!(world.lookup_name("player").unwrap(), player);
+/// ```
+///
+/// An entity can also use its own name as an alias. The name stays registered
+/// until both are gone.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let boss = world.spawn_empty().set_name("boss").add_alias("boss").id();
+///
+/// world.entity_mut(boss).remove_alias("boss");
+/// assert_eq!(world.lookup_name("boss").unwrap(), boss);
+///
+/// world.entity_mut(boss).add_alias("boss");
+/// world.despawn(boss);
+/// assert!(world.lookup_name("boss").is_err());
+///
+/// let new_boss = world.get_or_spawn_named("boss").id();
+/// assert_eq!(world.lookup_name("boss").unwrap(), new_boss);
+/// ```
+#[derive(Component, Clone, Default, Eq, PartialEq, Debug)]
+#[cfg_attr(
+    feature = "reflect",
+    derive(Reflect),
+    reflect(opaque, Component, Debug, Default, Clone, PartialEq)
+)]
+#[component(immutable)]
+#[component(on_insert = Aliases::on_insert)]
+#[component(on_replace = Aliases::on_replace)]
+pub struct Aliases(EstrSet);
+
+impl Aliases {
+    pub fn new<S: Into<Estr>>(aliases: impl IntoIterator<Item = S>) -> Aliases {
+        Aliases(aliases.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns a copy of these aliases with one more alias added.
+    pub fn with(&self, alias: impl Into<Estr>) -> Aliases {
+        let mut aliases = self.clone();
+        aliases.0.insert(alias.into());
+        aliases
+    }
+
+    /// Returns a copy of these aliases with an alias removed.
+    pub fn without(&self, alias: impl Into<Estr>) -> Aliases {
+        let mut aliases = self.clone();
+        aliases.0.remove(&alias.into());
+        aliases
+    }
+
+    fn on_insert(mut world: DeferredWorld, context: HookContext) {
+        let aliases = world
+            .entity(context.entity)
+            .get::<Aliases>()
+            .unwrap()
+            .clone();
+        if let Some(mut registry) = world.get_resource_mut::<Registry>() {
+            let taken = registry.register_aliases(context.entity, &aliases);
+            let error_handler = world.default_error_handler();
+            for error in taken {
+                error_handler(
+                    error.into(),
+                    bevy_ecs::error::ErrorContext::Observer {
+                        name: "Aliases::on_insert".into(),
+                        last_run: world.last_change_tick(),
+                    },
+                );
+            }
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                let taken = registry.register_aliases(context.entity, &aliases);
+                let error_handler = world.default_error_handler();
+                for error in taken {
+                    error_handler(
+                        error.into(),
+                        bevy_ecs::error::ErrorContext::Observer {
+                            name: "Aliases::on_insert".into(),
+                            last_run: world.last_change_tick(),
+                        },
+                    );
+                }
+            });
+        }
+    }
+
+    fn on_replace(mut world: DeferredWorld, context: HookContext) {
+        let aliases = world
+            .entity(context.entity)
+            .get::<Aliases>()
+            .unwrap()
+            .clone();
+        if let Some(mut registry) = world.get_resource_mut::<Registry>() {
+            registry.unregister_aliases(context.entity, &aliases);
+        } else {
+            world.commands().queue(move |world: &mut World| {
+                let mut registry = world.get_resource_or_init::<Registry>();
+                registry.unregister_aliases(context.entity, &aliases);
+            });
+        }
+    }
+}
+
+impl Deref for Aliases {
+    type Target = EstrSet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: Into<Estr>> FromIterator<S> for Aliases {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Aliases::new(iter)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // The Entity Registry
 
@@ -253,11 +655,101 @@ static EMPTY_SET: LazyLock<EntityHashSet> = LazyLock::new(EntityHashSet::default
 static EMPTY_REG: LazyLock<EntityRegistration> = LazyLock::new(EntityRegistration::default);
 
 /// Stores mappings from names and classes to entities.
+///
+/// The registry is kept up to date by component hooks on [`Identity`],
+/// [`Class`], [`Classes`], and [`Aliases`], rather than by a system. A name is
+/// registered as soon as its component is inserted, so it can be looked up
+/// straight away, even within the same system.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// world
+///     .run_system_once(|world: &mut World| {
+///         let aragorn = world.spawn_empty().set_identity("aragorn", "ranger").id();
+///         assert_eq!(world.lookup_name("aragorn").unwrap(), aragorn);
+///         assert!(world.lookup_class("ranger").contains(&aragorn));
+///     })
+///     .unwrap();
+/// ```
 #[derive(Resource, Default)]
 pub struct Registry {
     named_entities: EstrMap<Entity>,
     entity_classes: EstrMap<EntityHashSet>,
     reigrations: EntityHashMap<EntityRegistration>,
+    /// The previous [`Classes`] of entities whose `Classes` are being replaced.
+    replaced_classes: EntityHashMap<Classes>,
+    reserved_prefixes: ReservedNamePrefixes,
+    normalizer: Option<fn(&str) -> String>,
+}
+
+/// Returns a dotted class followed by each of its ancestors, from most to
+/// least specific.
+fn dotted_ancestors(class: Estr) -> impl Iterator<Item = &'static str> {
+    let class = class.as_str();
+    std::iter::once(class).chain(class.rmatch_indices('.').map(|(index, _)| &class[..index]))
+}
+
+/// Matches a name against a glob pattern supporting `*` and `?`.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` in the pattern, and the name position it
+    // was matched against, to backtrack to on a mismatch
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A list of name prefixes reserved for internal use.
+///
+/// Names starting with a reserved prefix cannot be assigned with
+/// [`set_name`][RegistryCommandsExt::set_name]. They can still be assigned by
+/// inserting an [`Identity`] component directly, which is considered a
+/// privileged path.
+#[derive(Default, Clone, Debug)]
+pub struct ReservedNamePrefixes(Vec<String>);
+
+impl ReservedNamePrefixes {
+    /// Reserves a name prefix.
+    pub fn reserve(&mut self, prefix: impl Into<String>) {
+        self.0.push(prefix.into());
+    }
+
+    /// Returns true if the name starts with a reserved prefix.
+    pub fn is_reserved(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|prefix| name.starts_with(prefix.as_str()))
+    }
+
+    /// Creates a borrowing iterator over the reserved prefixes.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
 }
 
 /// Stores name and class info about a specific entity.
@@ -265,11 +757,27 @@ pub struct Registry {
 pub struct EntityRegistration {
     pub name: Option<Estr>,
     pub class: Option<Estr>,
+    /// How many components index the entity under each name. An entity can
+    /// use its name as an alias as well, so it is only removed from the name
+    /// index once both are gone.
+    name_sources: EstrMap<u32>,
+    /// How many components index the entity under each class, for the same
+    /// reason: a class can be both its [`Class`] and one of its [`Classes`].
+    class_sources: EstrMap<u32>,
+}
+
+impl EntityRegistration {
+    fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.class.is_none()
+            && self.name_sources.is_empty()
+            && self.class_sources.is_empty()
+    }
 }
 
 impl Registry {
     pub fn lookup_name(&self, name: impl Into<Estr>) -> Result<Entity, EntityNotFoundError> {
-        let name = name.into();
+        let name = self.normalize(name);
         self.named_entities
             .get(&name)
             .copied()
@@ -282,7 +790,679 @@ impl Registry {
             .unwrap_or(&*EMPTY_SET)
     }
 
+    /// Creates a borrowing iterator over all registered names and
+    /// [`Aliases`], and the entities they belong to, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = (Estr, Entity)> + '_ {
+        self.named_entities
+            .iter()
+            .map(|(&name, &entity)| (name, entity))
+    }
+
+    /// Creates a borrowing iterator over all classes with at least one
+    /// entity, in no particular order.
+    pub fn classes(&self) -> impl Iterator<Item = &Estr> {
+        self.entity_classes
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(class, _)| class)
+    }
+
+    /// Returns the number of entities with a class, without iterating over
+    /// them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world.spawn(Class::new("zombie"));
+    /// world.spawn(Class::new("zombie"));
+    /// world.spawn(Class::new("ghoul"));
+    ///
+    /// assert_eq!(world.class_count("zombie"), 2);
+    /// assert_eq!(world.class_count("vampire"), 0);
+    ///
+    /// let registry = world.resource::<Registry>();
+    /// let mut counts: Vec<_> = registry.class_names_with_counts().collect();
+    /// counts.sort();
+    /// assert_eq!(counts, vec![("ghoul".into(), 1), ("zombie".into(), 2)]);
+    /// ```
+    pub fn class_count(&self, class: impl Into<Estr>) -> usize {
+        self.lookup_class(class).len()
+    }
+
+    /// Creates a borrowing iterator over all classes with at least one entity
+    /// and the number of entities in each, in no particular order.
+    pub fn class_names_with_counts(&self) -> impl Iterator<Item = (Estr, usize)> + '_ {
+        self.entity_classes
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(&class, entities)| (class, entities.len()))
+    }
+
+    /// Returns the members of class `a` that are not also members of class
+    /// `b`. Entities can be in more than one class through [`Classes`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let orc = world.spawn(Class::new("creature")).add_class("enemy").id();
+    /// let elf = world.spawn(Class::new("creature")).id();
+    /// world.spawn(Class::new("trap")).add_class("enemy");
+    ///
+    /// let friendly = world.class_difference("creature", "enemy");
+    /// assert_eq!(friendly.into_iter().collect::<Vec<_>>(), vec![elf]);
+    ///
+    /// let monsters = world.class_difference("enemy", "trap");
+    /// assert_eq!(monsters.into_iter().collect::<Vec<_>>(), vec![orc]);
+    /// ```
+    pub fn class_difference(&self, a: impl Into<Estr>, b: impl Into<Estr>) -> EntityHashSet {
+        let b = self.lookup_class(b);
+        self.lookup_class(a)
+            .iter()
+            .filter(|entity| !b.contains(*entity))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the entities that are members of every one of the classes.
+    /// Returns no entities if no classes are given.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let archer = world.spawn(Class::new("enemy")).add_class("ranged").id();
+    /// let orc = world.spawn(Class::new("enemy")).id();
+    /// let tower = world.spawn(Class::new("ranged")).id();
+    ///
+    /// let ranged_enemies: Vec<Entity> = world
+    ///     .entity_class_all(["enemy", "ranged"])
+    ///     .map(|entity| entity.id())
+    ///     .collect();
+    /// assert_eq!(ranged_enemies, vec![archer]);
+    ///
+    /// let threats = world.resource::<Registry>().class_union(["enemy", "ranged"]);
+    /// assert_eq!(threats.len(), 3);
+    /// assert!([archer, orc, tower].iter().all(|entity| threats.contains(entity)));
+    /// ```
+    pub fn class_intersection<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityHashSet {
+        let mut classes = classes.into_iter().map(|class| self.lookup_class(class));
+        let Some(first) = classes.next() else {
+            return EntityHashSet::new();
+        };
+        let rest: Vec<&EntityHashSet> = classes.collect();
+        first
+            .iter()
+            .filter(|entity| rest.iter().all(|class| class.contains(*entity)))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the entities that are members of at least one of the classes.
+    /// See [`class_intersection`][Registry::class_intersection].
+    pub fn class_union<S: Into<Estr>>(
+        &self,
+        classes: impl IntoIterator<Item = S>,
+    ) -> EntityHashSet {
+        classes
+            .into_iter()
+            .flat_map(|class| self.lookup_class(class).iter().copied())
+            .collect()
+    }
+
+    /// Returns every named entity whose name matches a simple glob pattern,
+    /// sorted by name. In the pattern, `*` matches any run of characters and
+    /// `?` matches any single character.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let orc = world.spawn_empty().set_name("enemy_orc").id();
+    /// let bat = world.spawn_empty().set_name("enemy_bat").id();
+    /// world.spawn_empty().set_name("friend_elf");
+    ///
+    /// assert_eq!(world.lookup_pattern("enemy_*"), vec![bat, orc]);
+    /// assert_eq!(world.lookup_pattern("enemy_?at"), vec![bat]);
+    /// ```
+    pub fn lookup_pattern(&self, pattern: &str) -> Vec<Entity> {
+        let mut matches: Vec<_> = self
+            .named_entities
+            .iter()
+            .filter(|(name, _)| glob_match(pattern, name))
+            .collect();
+        matches.sort_by_key(|(name, _)| name.as_str());
+        matches.into_iter().map(|(_, &entity)| entity).collect()
+    }
+
     pub fn lookup_entity(&self, entity: Entity) -> &EntityRegistration {
         self.reigrations.get(&entity).unwrap_or(&*EMPTY_REG)
     }
+
+    /// Returns every name an entity can be looked up by: its [`Identity`]
+    /// first, followed by its registered [`Aliases`] in sorted order. This
+    /// searches every registered name, so it is meant for tools and debugging
+    /// rather than hot paths.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let aragorn = world
+    ///     .spawn_empty()
+    ///     .set_name("aragorn")
+    ///     .add_alias("strider")
+    ///     .add_alias("elessar")
+    ///     .id();
+    ///
+    /// let names = world.resource::<Registry>().all_names_of(aragorn);
+    /// assert_eq!(names, ["aragorn", "elessar", "strider"]);
+    /// ```
+    pub fn all_names_of(&self, entity: Entity) -> Vec<Estr> {
+        let name = self.lookup_entity(entity).name;
+        let mut aliases: Vec<Estr> = self
+            .named_entities
+            .iter()
+            .filter(|&(&alias, &owner)| owner == entity && Some(alias) != name)
+            .map(|(&alias, _)| alias)
+            .collect();
+        aliases.sort_by_key(|alias| alias.as_str());
+        name.into_iter().chain(aliases).collect()
+    }
+
+    /// Returns the class of an entity followed by each of its ancestors, from
+    /// most to least specific. Classes form a hierarchy through dots, so
+    /// `"enemy.undead"` is the parent of `"enemy.undead.skeleton"`. Returns an
+    /// empty list if the entity has no class. Declared subclasses are not
+    /// followed; see [`ClassHierarchy::ancestors`] for those.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let skeleton = world.spawn(Class::new("enemy.undead.skeleton")).id();
+    ///
+    /// let ancestors = world.resource::<Registry>().class_ancestors(skeleton);
+    /// assert_eq!(ancestors, ["enemy.undead.skeleton", "enemy.undead", "enemy"]);
+    /// ```
+    pub fn class_ancestors(&self, entity: Entity) -> Vec<Estr> {
+        let Some(class) = self.lookup_entity(entity).class else {
+            return Vec::new();
+        };
+        dotted_ancestors(class).map(Estr::from).collect()
+    }
+
+    /// Clears the name of an entity's registration, dropping the registration
+    /// once it is empty.
+    fn unregister_name(&mut self, entity: Entity) {
+        if let Some(registration) = self.reigrations.get_mut(&entity) {
+            registration.name = None;
+        }
+        self.drop_empty_registration(entity);
+    }
+
+    /// Clears the class of an entity's registration, dropping the registration
+    /// once it is empty.
+    fn unregister_class(&mut self, entity: Entity) {
+        if let Some(registration) = self.reigrations.get_mut(&entity) {
+            registration.class = None;
+        }
+        self.drop_empty_registration(entity);
+    }
+
+    /// Adds aliases of an entity to the name index, returning an error for
+    /// each alias that is already used by another entity.
+    fn register_aliases(&mut self, entity: Entity, aliases: &Aliases) -> Vec<AliasTakenError> {
+        let mut taken = Vec::new();
+        for &alias in aliases.iter() {
+            let alias = self.normalize(alias);
+            match self.named_entities.get(&alias) {
+                Some(&owner) if owner != entity => taken.push(AliasTakenError {
+                    alias,
+                    owner,
+                    requester: entity,
+                }),
+                _ => self.add_name_source(entity, alias),
+            }
+        }
+        taken
+    }
+
+    /// Removes aliases of an entity from the name index.
+    fn unregister_aliases(&mut self, entity: Entity, aliases: &Aliases) {
+        for &alias in aliases.iter() {
+            let alias = self.normalize(alias);
+            self.remove_name_source(entity, alias);
+        }
+    }
+
+    /// Drops an entity's registration once nothing refers to it.
+    fn drop_empty_registration(&mut self, entity: Entity) {
+        if self
+            .reigrations
+            .get(&entity)
+            .is_some_and(EntityRegistration::is_empty)
+        {
+            self.reigrations.remove(&entity);
+        }
+    }
+
+    /// Indexes an entity under a name for one more component.
+    fn add_name_source(&mut self, entity: Entity, name: Estr) {
+        let registration = self.reigrations.entry(entity).or_default();
+        *registration.name_sources.entry(name).or_default() += 1;
+        self.named_entities.insert(name, entity);
+    }
+
+    /// Indexes an entity under a name for one less component, removing it
+    /// from the index when no components are left. Does nothing if the entity
+    /// was not indexed under the name (for example, if it was taken).
+    fn remove_name_source(&mut self, entity: Entity, name: Estr) {
+        let Some(registration) = self.reigrations.get_mut(&entity) else {
+            return;
+        };
+        let Some(sources) = registration.name_sources.get_mut(&name) else {
+            return;
+        };
+        *sources -= 1;
+        if *sources == 0 {
+            registration.name_sources.remove(&name);
+            if self.named_entities.get(&name) == Some(&entity) {
+                self.named_entities.remove(&name);
+            }
+            self.drop_empty_registration(entity);
+        }
+    }
+
+    /// Indexes an entity under a class for one more component.
+    fn add_class_source(&mut self, entity: Entity, class: Estr) {
+        let registration = self.reigrations.entry(entity).or_default();
+        *registration.class_sources.entry(class).or_default() += 1;
+        self.entity_classes.entry(class).or_default().insert(entity);
+    }
+
+    /// Indexes an entity under a class for one less component, removing it
+    /// from the index when no components are left.
+    fn remove_class_source(&mut self, entity: Entity, class: Estr) {
+        let Some(registration) = self.reigrations.get_mut(&entity) else {
+            return;
+        };
+        let Some(sources) = registration.class_sources.get_mut(&class) else {
+            return;
+        };
+        *sources -= 1;
+        if *sources == 0 {
+            registration.class_sources.remove(&class);
+            if let Some(entities) = self.entity_classes.get_mut(&class) {
+                entities.remove(&entity);
+            }
+            self.drop_empty_registration(entity);
+        }
+    }
+
+    /// Updates the class index for an entity's new [`Classes`], comparing
+    /// them with the classes it had before they were replaced.
+    fn update_classes(&mut self, entity: Entity, classes: &Classes) {
+        let old = self.replaced_classes.remove(&entity).unwrap_or_default();
+        for &class in old.difference(classes) {
+            self.remove_class_source(entity, class);
+        }
+        for &class in classes.difference(&old) {
+            self.add_class_source(entity, class);
+        }
+    }
+
+    pub fn reserved_prefixes(&self) -> &ReservedNamePrefixes {
+        &self.reserved_prefixes
+    }
+
+    pub fn reserved_prefixes_mut(&mut self) -> &mut ReservedNamePrefixes {
+        &mut self.reserved_prefixes
+    }
+
+    /// Sets a function applied to names when they are stored in and looked
+    /// up from the name index, so that names which differ only in ways the
+    /// project doesn't care about refer to the same entity. By default names
+    /// are used as given.
+    ///
+    /// The normalizer applies to every name and alias, including those from
+    /// an [`Identity`] or [`Aliases`] component inserted directly.
+    /// [`set_name`], [`set_identity`], and [`add_alias`] also store the
+    /// normalized name in the component. Because the index is keyed by
+    /// normalized names, the normalizer can't be changed once any names are
+    /// registered; set it before naming any entities.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// world
+    ///     .resource_mut::<Registry>()
+    ///     .set_normalizer(|name| name.trim().replace(' ', "_"))
+    ///     .unwrap();
+    ///
+    /// let boss = world.spawn_empty().set_name("  big boss ").id();
+    /// assert_eq!(world.get::<Identity>(boss).unwrap().as_str(), "big_boss");
+    /// assert_eq!(world.lookup_name("big boss").unwrap(), boss);
+    /// assert_eq!(world.lookup_name("big_boss").unwrap(), boss);
+    ///
+    /// // names inserted directly are normalized too
+    /// let guard = world.spawn(Identity::new(" Guard ")).id();
+    /// assert_eq!(world.lookup_name(" Guard ").unwrap(), guard);
+    /// assert_eq!(world.lookup_name("Guard").unwrap(), guard);
+    ///
+    /// let mut registry = world.resource_mut::<Registry>();
+    /// assert!(registry.set_normalizer(|name| name.to_lowercase()).is_err());
+    /// ```
+    ///
+    /// [`set_name`]: RegistryCommandsExt::set_name
+    /// [`set_identity`]: RegistryCommandsExt::set_identity
+    /// [`add_alias`]: RegistryCommandsExt::add_alias
+    pub fn set_normalizer(
+        &mut self,
+        normalizer: fn(&str) -> String,
+    ) -> Result<(), NormalizerLockedError> {
+        if !self.named_entities.is_empty() {
+            return Err(NormalizerLockedError);
+        }
+        self.normalizer = Some(normalizer);
+        Ok(())
+    }
+
+    /// Removes the normalizer, so names are used as given. Like
+    /// [`set_normalizer`][Registry::set_normalizer], this fails once any names
+    /// are registered.
+    pub fn clear_normalizer(&mut self) -> Result<(), NormalizerLockedError> {
+        if !self.named_entities.is_empty() {
+            return Err(NormalizerLockedError);
+        }
+        self.normalizer = None;
+        Ok(())
+    }
+
+    /// Applies the normalizer set with
+    /// [`set_normalizer`][Registry::set_normalizer] to a name.
+    pub fn normalize(&self, name: impl Into<Estr>) -> Estr {
+        let name = name.into();
+        match self.normalizer {
+            Some(normalizer) => Estr::from(normalizer(name.as_str()).as_str()),
+            None => name,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Automatic Naming
+
+/// The next index to try when naming an entity after each class.
+#[derive(Resource, Default)]
+struct ClassNameCounters(EstrMap<usize>);
+
+/// An observer which names entities after their [`Class`], as `{class}_0`,
+/// `{class}_1`, and so on, when they are given a class but have no
+/// [`Identity`]. Indices already in use are skipped.
+///
+/// This is added by
+/// [`PropsPlugin::auto_name_by_class`][crate::plugin::PropsPlugin::auto_name_by_class].
+/// Names are assigned when commands are next applied, so an entity which is
+/// given a class and then named by hand keeps its own name.
+pub fn name_by_class(insert: On<Insert, Class>, mut commands: Commands) {
+    let entity = insert.entity;
+    commands.queue(move |world: &mut World| {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            return;
+        };
+        if entity_ref.contains::<Identity>() {
+            return;
+        }
+        let Some(&Class(class)) = entity_ref.get::<Class>() else {
+            return;
+        };
+        let mut index = world
+            .get_resource_or_init::<ClassNameCounters>()
+            .0
+            .get(&class)
+            .copied()
+            .unwrap_or_default();
+        let name = loop {
+            let name = format!("{class}_{index}");
+            index += 1;
+            if world.lookup_name(name.as_str()).is_err() {
+                break name;
+            }
+        };
+        world
+            .resource_mut::<ClassNameCounters>()
+            .0
+            .insert(class, index);
+        world.entity_mut(entity).set_name(name);
+    });
+}
+
+// -----------------------------------------------------------------------------
+// Class Hierarchy
+
+/// Declares subclass relationships between classes.
+///
+/// Classes also form a hierarchy through dots, as in
+/// [`Registry::class_ancestors`]: `"enemy.undead"` is implicitly a subclass of
+/// `"enemy"`. The hierarchy combines both, so a class's parents are the class
+/// its dotted name nests in, followed by the classes it was declared a
+/// subclass of.
+///
+/// Subclasses are used by the "deep" class lookups, such as
+/// [`entity_class_deep`][RegistryLookupExt::entity_class_deep], which return
+/// entities of a class and of all its subclasses, transitively, and by
+/// [`ClassDefaults`]. The plain lookups ignore the hierarchy.
+///
+/// Cycles are allowed: each class is visited at most once, so the classes in a
+/// cycle simply count as subclasses of one another.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let mut hierarchy = ClassHierarchy::default();
+/// hierarchy.declare_subclass("dog", "mammal");
+/// hierarchy.declare_subclass("mammal", "animal");
+/// hierarchy.declare_subclass("enemy.undead", "monster");
+/// world.insert_resource(hierarchy);
+///
+/// let rex = world.spawn_empty().set_class("dog").id();
+/// let skeleton = world.spawn_empty().set_class("enemy.undead.skeleton").id();
+///
+/// assert!(world.lookup_class("animal").is_empty());
+/// assert!(world.lookup_class_deep("animal").contains(&rex));
+/// assert!(world.lookup_class_deep("enemy").contains(&skeleton));
+/// assert!(world.lookup_class_deep("monster").contains(&skeleton));
+///
+/// let hierarchy = world.resource::<ClassHierarchy>();
+/// assert_eq!(
+///     hierarchy.ancestors("enemy.undead.skeleton"),
+///     ["enemy.undead.skeleton", "enemy.undead", "enemy", "monster"],
+/// );
+/// ```
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ClassHierarchy {
+    subclasses: EstrMap<EstrSet>,
+    parents: EstrMap<EstrSet>,
+}
+
+impl ClassHierarchy {
+    /// Declares `child` to be a direct subclass of `parent`.
+    pub fn declare_subclass(&mut self, child: impl Into<Estr>, parent: impl Into<Estr>) {
+        let (child, parent) = (child.into(), parent.into());
+        self.subclasses.entry(parent).or_default().insert(child);
+        self.parents.entry(child).or_default().insert(parent);
+    }
+
+    /// Returns the subclasses declared directly for a class. Subclasses
+    /// implied by dotted names are not included.
+    pub fn subclasses(&self, class: impl Into<Estr>) -> impl Iterator<Item = Estr> + '_ {
+        self.subclasses
+            .get(&class.into())
+            .into_iter()
+            .flat_map(|subclasses| subclasses.iter().copied())
+    }
+
+    /// Returns the direct parents of a class: the class its dotted name nests
+    /// in, if any, followed by the classes it was declared a subclass of,
+    /// sorted by name.
+    pub fn parents(&self, class: impl Into<Estr>) -> Vec<Estr> {
+        let class = class.into();
+        let dotted = dotted_ancestors(class).nth(1).map(Estr::from);
+        let mut declared: Vec<Estr> = self
+            .parents
+            .get(&class)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&parent| Some(parent) != dotted)
+            .collect();
+        declared.sort_by_key(|parent| parent.as_str());
+        dotted.into_iter().chain(declared).collect()
+    }
+
+    /// Returns a class followed by all its ancestors, from most to least
+    /// specific. Ancestors are visited breadth first, in the order of
+    /// [`parents`][ClassHierarchy::parents], and each appears once.
+    pub fn ancestors(&self, class: impl Into<Estr>) -> Vec<Estr> {
+        let mut ancestors = vec![class.into()];
+        let mut visited: EstrSet = ancestors.iter().copied().collect();
+        let mut next = 0;
+        while let Some(&class) = ancestors.get(next) {
+            for parent in self.parents(class) {
+                if visited.insert(parent) {
+                    ancestors.push(parent);
+                }
+            }
+            next += 1;
+        }
+        ancestors
+    }
+
+    /// Returns a class together with all its subclasses, transitively.
+    ///
+    /// The hierarchy only knows the classes named in a declaration, so
+    /// subclasses implied by dotted names are found only among those. Use
+    /// [`Registry::lookup_class_deep`] to search every class in use.
+    pub fn descendants(&self, class: impl Into<Estr>) -> EstrSet {
+        let class = class.into();
+        let mut descendants: EstrSet = self
+            .subclasses
+            .keys()
+            .chain(self.parents.keys())
+            .copied()
+            .filter(|&known| self.is_subclass_of(known, class))
+            .collect();
+        descendants.insert(class);
+        descendants
+    }
+
+    /// Returns true if `class` is `ancestor` or one of its subclasses.
+    pub fn is_subclass_of(&self, class: impl Into<Estr>, ancestor: impl Into<Estr>) -> bool {
+        self.ancestors(class).contains(&ancestor.into())
+    }
+}
+
+impl Registry {
+    /// Returns the entities of a class and of all its subclasses, both
+    /// declared and implied by dotted names.
+    pub fn lookup_class_deep(
+        &self,
+        class: impl Into<Estr>,
+        hierarchy: &ClassHierarchy,
+    ) -> EntityHashSet {
+        let class = class.into();
+        self.entity_classes
+            .iter()
+            .filter(|&(&subclass, _)| hierarchy.is_subclass_of(subclass, class))
+            .flat_map(|(_, entities)| entities.iter().copied())
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Class Defaults
+
+/// Default property values for each class.
+///
+/// Defaults are inherited down the [`ClassHierarchy`], through both dotted
+/// names and declared subclasses: an `"enemy.undead.skeleton"` receives the
+/// defaults of `"enemy"`, overridden by those of `"enemy.undead"`, overridden
+/// in turn by its own. Each class is searched in the order of
+/// [`ClassHierarchy::ancestors`]. Defaults are applied by
+/// [`resolve_prop`][ClassDefaultsExt::resolve_prop], where the entity's own
+/// props override all of them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let mut defaults = ClassDefaults::default();
+/// defaults.insert("enemy", Props::new().with("hp", 10.0).with("armor", 1.0).with("speed", 1.0));
+/// defaults.insert("enemy.undead", Props::new().with("hp", 20.0).with("armor", 2.0));
+/// defaults.insert("enemy.undead.skeleton", Props::new().with("hp", 30.0));
+/// world.insert_resource(defaults);
+///
+/// let skeleton = world.spawn_empty()
+///     .set_class("enemy.undead.skeleton")
+///     .set_prop("speed", 4.0)
+///     .id();
+///
+/// assert_eq!(world.resolve_prop::<f32>(skeleton, "hp"), 30.0);
+/// assert_eq!(world.resolve_prop::<f32>(skeleton, "armor"), 2.0);
+/// assert_eq!(world.resolve_prop::<f32>(skeleton, "speed"), 4.0);
+///
+/// let mut hierarchy = ClassHierarchy::default();
+/// hierarchy.declare_subclass("enemy.undead", "cursed");
+/// world.insert_resource(hierarchy);
+/// world.resource_mut::<ClassDefaults>().insert("cursed", Props::new().with("gold", 0.0).with("armor", 5.0));
+/// assert_eq!(world.resolve_prop::<f32>(skeleton, "gold"), 0.0);
+/// assert_eq!(world.resolve_prop::<f32>(skeleton, "armor"), 2.0);
+/// ```
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ClassDefaults {
+    defaults: EstrMap<Props>,
+}
+
+impl ClassDefaults {
+    /// Sets the default props of a class, replacing any previous defaults.
+    pub fn insert(&mut self, class: impl Into<Estr>, props: Props) {
+        self.defaults.insert(class.into(), props);
+    }
+
+    /// Returns the default props declared for exactly this class.
+    pub fn get(&self, class: impl Into<Estr>) -> Option<&Props> {
+        self.defaults.get(&class.into())
+    }
+
+    /// Returns the default value of a property for a class, searching from the
+    /// class itself up through its [`ancestors`][ClassHierarchy::ancestors].
+    pub fn resolve(
+        &self,
+        class: impl Into<Estr>,
+        name: impl Into<Estr>,
+        hierarchy: &ClassHierarchy,
+    ) -> Option<Value> {
+        let name = name.into();
+        hierarchy
+            .ancestors(class)
+            .into_iter()
+            .filter_map(|class| self.defaults.get(&class))
+            .find_map(|props| props.get_value(name))
+    }
 }
@@ -0,0 +1,56 @@
+//! Defines a system param for looking up named entities.
+
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, Res, SystemParam},
+    world::EntityRef,
+};
+use estr::Estr;
+
+use super::Registry;
+
+/// Looks up named entities from an ordinary system, without exclusive world
+/// access.
+///
+/// This is read-only, but [`entity`][NamedEntities::entity] can return any
+/// entity, so the param reads every component. A system using it cannot also
+/// have mutable access to components, and will not run in parallel with
+/// systems that write to components. When only ids are needed, prefer
+/// [`get`][NamedEntities::get] alongside a narrower query, or use
+/// `Res<Registry>` directly.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # use bevy_mod_props::prelude::*;
+/// let mut world = World::new();
+/// world.init_resource::<Registry>();
+/// let gandalf = world.spawn_empty()
+///     .set_name("gandalf")
+///     .set_prop("likes_elves", true)
+///     .id();
+///
+/// let found = world.run_system_once(move |named: NamedEntities| {
+///     assert_eq!(named.get("gandalf"), Some(gandalf));
+///     assert!(named.get("sauron").is_none());
+///     named.entity("gandalf").unwrap().get_prop::<bool>("likes_elves")
+/// });
+/// assert!(found.unwrap());
+/// ```
+#[derive(SystemParam)]
+pub struct NamedEntities<'w, 's> {
+    registry: Option<Res<'w, Registry>>,
+    entities: Query<'w, 's, EntityRef<'static>>,
+}
+
+impl<'w, 's> NamedEntities<'w, 's> {
+    /// Returns the entity with a name, if there is one.
+    pub fn get(&self, name: impl Into<Estr>) -> Option<Entity> {
+        self.registry.as_ref()?.lookup_name(name).ok()
+    }
+
+    /// Returns the entity with a name, if there is one and it still exists.
+    pub fn entity(&self, name: impl Into<Estr>) -> Option<EntityRef<'_>> {
+        self.entities.get(self.get(name)?).ok()
+    }
+}
@@ -0,0 +1,220 @@
+//! Saves and loads named entities, along with their classes, aliases, props,
+//! and links, behind the `serialize` feature.
+//!
+//! A [`PropsScene`] stores entities by name, and stores links by the names of
+//! their targets rather than by raw entity ids. This keeps scene files easy to
+//! author by hand, and means links survive being saved and loaded.
+//!
+//! ```ron
+//! (
+//!     entities: [
+//!         (name: "bilbo", class: Some("hobbit"), props: {"health": 100.0}, links: {"talking_to": ["gandalf"]}),
+//!         (name: "gandalf", class: Some("wizard"), classes: ["istari"], aliases: ["mithrandir"]),
+//!     ],
+//! )
+//! ```
+
+use std::collections::BTreeMap;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+use estr::{Estr, EstrSet};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::links::{Links, LinksCommandsExt};
+use crate::props::Props;
+use crate::registry::{
+    Aliases, Class, Classes, EntityNotFoundError, Identity, RegistryCommandsExt, RegistryLookupExt,
+    RegistryTryCommandsExt, SetNameError,
+};
+
+// -----------------------------------------------------------------------------
+// Errors
+
+#[derive(Debug, Error)]
+#[error("cannot save link '{link}' from '{name}': target {target} has no name")]
+pub struct UnnamedLinkTargetError {
+    name: Estr,
+    link: Estr,
+    target: Entity,
+}
+
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub enum SceneSpawnError {
+    SetName(#[from] SetNameError),
+    EntityNotFound(#[from] EntityNotFoundError),
+}
+
+// -----------------------------------------------------------------------------
+// Scenes
+
+/// A serializable collection of named entities.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct PropsScene {
+    pub entities: Vec<SceneEntity>,
+}
+
+/// A single named entity in a [`PropsScene`].
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct SceneEntity {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// Further classes, as in [`Classes`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classes: Vec<String>,
+    /// Other names for this entity, as in [`Aliases`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub props: Props,
+    /// Links from this entity, by link name and then by target name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, Vec<String>>,
+}
+
+impl PropsScene {
+    /// Captures every named entity in the world. Links to despawned entities
+    /// are skipped, and links to entities without names are an error.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # use bevy_mod_props::scene::PropsScene;
+    /// let mut world = World::new();
+    /// world.init_resource::<Registry>();
+    /// let gandalf = world.spawn_empty()
+    ///     .set_identity("gandalf", "wizard")
+    ///     .add_class("istari")
+    ///     .add_alias("mithrandir")
+    ///     .id();
+    /// world.spawn_empty()
+    ///     .set_name("bilbo")
+    ///     .set_prop("health", 100.0)
+    ///     .set_link("talking_to", gandalf);
+    ///
+    /// let scene = PropsScene::from_world(&world).unwrap();
+    /// let text = ron::to_string(&scene).unwrap();
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.init_resource::<Registry>();
+    /// let scene: PropsScene = ron::from_str(&text).unwrap();
+    /// scene.spawn(&mut loaded).unwrap();
+    ///
+    /// let bilbo = loaded.entity_named("bilbo").unwrap();
+    /// assert_eq!(bilbo.get_prop::<f32>("health"), 100.0);
+    /// assert_eq!(bilbo.get_linked("talking_to"), loaded.lookup_name("gandalf").ok());
+    /// assert_eq!(loaded.lookup_name("mithrandir").ok(), loaded.lookup_name("gandalf").ok());
+    /// assert_eq!(loaded.lookup_class("istari").len(), 1);
+    /// ```
+    pub fn from_world(world: &World) -> Result<PropsScene, UnnamedLinkTargetError> {
+        let Some(mut query) = world.try_query::<(
+            &Identity,
+            Option<&Class>,
+            Option<&Classes>,
+            Option<&Aliases>,
+            Option<&Props>,
+            Option<&Links>,
+        )>() else {
+            return Ok(PropsScene::default());
+        };
+        let mut entities = Vec::new();
+        for (identity, class, classes, aliases, props, links) in query.iter(world) {
+            let name = **identity;
+            let mut scene_links = BTreeMap::new();
+            for (link, targets) in links.into_iter().flat_map(Links::iter) {
+                let mut target_names = Vec::new();
+                for &target in targets {
+                    let Ok(target_ref) = world.get_entity(target) else {
+                        continue;
+                    };
+                    let Some(target_name) = target_ref.get::<Identity>() else {
+                        return Err(UnnamedLinkTargetError { name, link, target });
+                    };
+                    target_names.push(target_name.to_string());
+                }
+                target_names.sort();
+                scene_links.insert(link.to_string(), target_names);
+            }
+            entities.push(SceneEntity {
+                name: name.to_string(),
+                class: class.map(|class| class.to_string()),
+                classes: sorted_names(classes.map(|classes| &**classes)),
+                aliases: sorted_names(aliases.map(|aliases| &**aliases)),
+                props: props.cloned().unwrap_or_default(),
+                links: scene_links,
+            });
+        }
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(PropsScene { entities })
+    }
+
+    /// Spawns the entities in the scene, and then links them together. Link
+    /// targets are looked up in the registry once every entity has been
+    /// spawned, so they may refer to entities in the scene or to named
+    /// entities already in the world.
+    ///
+    /// Returns the spawned entities, in scene order. If a name is already
+    /// taken, the entity which wanted it is despawned and the error is
+    /// returned, leaving the entities spawned before it. If a link target
+    /// cannot be found, the entities remain spawned but the error is returned.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_props::prelude::*;
+    /// # use bevy_mod_props::scene::{PropsScene, SceneEntity, SceneSpawnError};
+    /// let mut world = World::new();
+    /// let scene = PropsScene {
+    ///     entities: vec![SceneEntity { name: "smaug".into(), ..Default::default() }],
+    /// };
+    ///
+    /// let smaug = scene.spawn(&mut world).unwrap()[0];
+    /// let result = scene.spawn(&mut world);
+    /// assert!(matches!(result, Err(SceneSpawnError::SetName(SetNameError::NameTaken(_)))));
+    /// assert_eq!(world.lookup_name("smaug").unwrap(), smaug);
+    /// ```
+    pub fn spawn(&self, world: &mut World) -> Result<Vec<Entity>, SceneSpawnError> {
+        let mut spawned = Vec::with_capacity(self.entities.len());
+        for scene_entity in &self.entities {
+            let mut entity = world.spawn(scene_entity.props.clone());
+            if let Err(error) = entity.try_set_name(scene_entity.name.as_str()) {
+                entity.despawn();
+                return Err(error.into());
+            }
+            if let Some(class) = &scene_entity.class {
+                entity.set_class(class.as_str());
+            }
+            for class in &scene_entity.classes {
+                entity.add_class(class.as_str());
+            }
+            for alias in &scene_entity.aliases {
+                entity.add_alias(alias.as_str());
+            }
+            spawned.push(entity.id());
+        }
+        world.flush();
+        for (scene_entity, &entity) in self.entities.iter().zip(&spawned) {
+            for (link, target_names) in &scene_entity.links {
+                for target_name in target_names {
+                    let target = world.lookup_name(target_name.as_str())?;
+                    world.entity_mut(entity).add_link(link.as_str(), target);
+                }
+            }
+        }
+        Ok(spawned)
+    }
+}
+
+/// Returns a set of names as sorted strings, so scenes are saved in a stable
+/// order.
+fn sorted_names(names: Option<&EstrSet>) -> Vec<String> {
+    let mut names: Vec<String> = names
+        .into_iter()
+        .flatten()
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+    names
+}